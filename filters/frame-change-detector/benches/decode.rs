@@ -0,0 +1,55 @@
+// THEORY:
+// Decode-backend comparison for `DecoderBackend::Image` and `DecoderBackend::TurboJpeg` on
+// a 1080p JPEG. Frames go through `CoreDetector::analyze_image_bytes` with `profile` on, and
+// only the decode stages (`image_load` plus `grayscale`) are reported, so the pipeline's own
+// cost doesn't dilute the difference. Run with:
+//
+//     cargo bench --bench decode --features turbojpeg
+//
+// Without the `turbojpeg` feature the second row measures the `Image` fallback.
+
+use std::time::Instant;
+
+use frame_change_detector::{CoreDetector, DecoderBackend, DetectorConfig};
+
+const WIDTH: u32 = 1920;
+const HEIGHT: u32 = 1080;
+const FRAMES: usize = 30;
+
+/// A color 1080p JPEG with gradients and fine texture, so the entropy decoder has real work
+fn sample_jpeg() -> Vec<u8> {
+    let image = image::RgbImage::from_fn(WIDTH, HEIGHT, |x, y| {
+        let texture = ((x * 7 + y * 13) % 23) as u8;
+        image::Rgb([(x / 8) as u8 ^ texture, (y / 5) as u8, ((x + y) / 12) as u8 + texture])
+    });
+    let mut data = Vec::new();
+    image::DynamicImage::ImageRgb8(image)
+        .write_to(&mut std::io::Cursor::new(&mut data), image::ImageFormat::Jpeg)
+        .expect("JPEG encoding");
+    data
+}
+
+fn main() {
+    let jpeg = sample_jpeg();
+    println!("{}x{} JPEG, {} KiB, {} frames per backend", WIDTH, HEIGHT, jpeg.len() / 1024, FRAMES);
+    for backend in [DecoderBackend::Image, DecoderBackend::TurboJpeg] {
+        let config = DetectorConfig { decoder_backend: backend, profile: true, ..Default::default() };
+        let mut detector = CoreDetector::new(config).expect("valid config");
+        let started = Instant::now();
+        let mut decode_ms = Vec::with_capacity(FRAMES);
+        for frame in 0..FRAMES {
+            let analysis = detector.analyze_image_bytes(&jpeg, frame as u64 * 33).expect("decodable frame");
+            let stages = analysis.stage_timings_ms.expect("profile is on");
+            decode_ms.push(stages.get("image_load").copied().unwrap_or(0.0) + stages.get("grayscale").copied().unwrap_or(0.0));
+        }
+        decode_ms.sort_by(f64::total_cmp);
+        println!(
+            "{:?} (in use: {:?}): decode median {:.2} ms, min {:.2} ms; whole frame {:.2} ms",
+            backend,
+            detector.decoder_backend(),
+            decode_ms[FRAMES / 2],
+            decode_ms[0],
+            started.elapsed().as_secs_f64() * 1000.0 / FRAMES as f64
+        );
+    }
+}
@@ -0,0 +1,343 @@
+// THEORY:
+// The `arrow` module exports batch results as an Arrow record batch (`arrow` cargo
+// feature, which implies `python`). Analytics consumers (pyarrow, polars, duckdb, pandas)
+// want columns rather than a list of `FrameAnalysis` objects, and building those columns
+// in Python costs one attribute lookup per field per frame. Instead of pulling in the
+// arrow crates, the batch is handed over through the Arrow C Data Interface and its
+// PyCapsule protocol (`__arrow_c_schema__`, `__arrow_c_array__`, `__arrow_c_stream__`):
+// the structs are a fixed C ABI, the schema is fixed too, and every Arrow implementation
+// imports them without copying.
+//
+// Ownership follows the C Data Interface spec: every exported struct carries a `release`
+// callback and owns its buffers through `private_data`. A consumer that imports a struct
+// moves it out of the capsule and marks the capsule's copy released; a capsule that was
+// never imported releases its struct when it is garbage collected.
+
+use std::ffi::{c_char, c_int, c_void, CStr};
+use std::ptr;
+
+use pyo3::prelude::*;
+use pyo3::types::PyCapsule;
+
+use crate::FrameAnalysis;
+
+const SCHEMA_CAPSULE_NAME: &CStr = c"arrow_schema";
+const ARRAY_CAPSULE_NAME: &CStr = c"arrow_array";
+const STREAM_CAPSULE_NAME: &CStr = c"arrow_array_stream";
+
+// Column names and Arrow format strings, in schema order (see README "Arrow Results")
+const COLUMNS: [(&CStr, &CStr); 7] = [
+    (c"frame_count", c"L"),        // uint64
+    (c"timestamp_ms", c"L"),       // uint64
+    (c"scene_state", c"u"),        // utf8
+    (c"should_trigger", c"b"),     // bool
+    (c"confidence", c"f"),         // float32
+    (c"disturbed_fraction", c"f"), // float32
+    (c"blob_count", c"L"),         // uint64
+];
+
+#[repr(C)]
+struct ArrowSchema {
+    format: *const c_char,
+    name: *const c_char,
+    metadata: *const c_char,
+    flags: i64,
+    n_children: i64,
+    children: *mut *mut ArrowSchema,
+    dictionary: *mut ArrowSchema,
+    release: Option<unsafe extern "C" fn(*mut ArrowSchema)>,
+    private_data: *mut c_void,
+}
+
+#[repr(C)]
+struct ArrowArray {
+    length: i64,
+    null_count: i64,
+    offset: i64,
+    n_buffers: i64,
+    n_children: i64,
+    buffers: *mut *const c_void,
+    children: *mut *mut ArrowArray,
+    dictionary: *mut ArrowArray,
+    release: Option<unsafe extern "C" fn(*mut ArrowArray)>,
+    private_data: *mut c_void,
+}
+
+#[repr(C)]
+struct ArrowArrayStream {
+    get_schema: Option<unsafe extern "C" fn(*mut ArrowArrayStream, *mut ArrowSchema) -> c_int>,
+    get_next: Option<unsafe extern "C" fn(*mut ArrowArrayStream, *mut ArrowArray) -> c_int>,
+    get_last_error: Option<unsafe extern "C" fn(*mut ArrowArrayStream) -> *const c_char>,
+    release: Option<unsafe extern "C" fn(*mut ArrowArrayStream)>,
+    private_data: *mut c_void,
+}
+
+// The structs only hold pointers into their own `private_data`, which nothing else shares
+unsafe impl Send for ArrowSchema {}
+unsafe impl Send for ArrowArray {}
+unsafe impl Send for ArrowArrayStream {}
+
+struct SchemaPrivate {
+    children: Vec<*mut ArrowSchema>,
+}
+
+struct ArrayPrivate {
+    _data: Vec<Vec<u64>>, // Buffer storage, u64 words so every buffer is 8-byte aligned
+    buffers: Vec<*const c_void>,
+    children: Vec<*mut ArrowArray>,
+}
+
+struct StreamPrivate {
+    batch: AnalysisBatch,
+    exhausted: bool,
+}
+
+/// Columnar copy of a batch of results, exported through the Arrow PyCapsule protocol;
+/// pass it to `pyarrow.record_batch`, `polars.DataFrame`, `duckdb` and friends
+#[pyclass(module = "frame_change_detector")]
+#[derive(Clone)]
+pub struct AnalysisBatch {
+    frame_count: Vec<u64>,
+    timestamp_ms: Vec<u64>,
+    scene_state: Vec<String>,
+    should_trigger: Vec<bool>,
+    confidence: Vec<f32>,
+    disturbed_fraction: Vec<f32>,
+    blob_count: Vec<u64>,
+}
+
+impl AnalysisBatch {
+    pub(crate) fn new(results: &[FrameAnalysis]) -> Self {
+        Self {
+            frame_count: results.iter().map(|r| r.frame_count).collect(),
+            timestamp_ms: results.iter().map(|r| r.timestamp_ms).collect(),
+            scene_state: results.iter().map(|r| r.scene_state.clone()).collect(),
+            should_trigger: results.iter().map(|r| r.should_trigger).collect(),
+            confidence: results.iter().map(|r| r.confidence).collect(),
+            disturbed_fraction: results.iter().map(|r| r.disturbed_fraction).collect(),
+            blob_count: results.iter().map(|r| r.total_blobs as u64).collect(),
+        }
+    }
+
+    fn num_rows(&self) -> usize {
+        self.frame_count.len()
+    }
+
+    /// Struct array of all columns, the C Data Interface form of a record batch
+    fn export_array(&self) -> ArrowArray {
+        let rows = self.num_rows();
+        let mut offsets = Vec::with_capacity(rows + 1);
+        let mut text = Vec::new();
+        offsets.push(0i32);
+        for state in &self.scene_state {
+            text.extend_from_slice(state.as_bytes());
+            offsets.push(text.len() as i32);
+        }
+        let mut bits = vec![0u8; rows.div_ceil(8)];
+        for (row, _) in self.should_trigger.iter().enumerate().filter(|(_, &set)| set) {
+            bits[row / 8] |= 1 << (row % 8);
+        }
+
+        let children = vec![
+            leaf_array(rows, vec![words(&self.frame_count, |v| v.to_le_bytes())]),
+            leaf_array(rows, vec![words(&self.timestamp_ms, |v| v.to_le_bytes())]),
+            leaf_array(rows, vec![words(&offsets, |v| v.to_le_bytes()), words(&text, |v| [*v])]),
+            leaf_array(rows, vec![words(&bits, |v| [*v])]),
+            leaf_array(rows, vec![words(&self.confidence, |v| v.to_le_bytes())]),
+            leaf_array(rows, vec![words(&self.disturbed_fraction, |v| v.to_le_bytes())]),
+            leaf_array(rows, vec![words(&self.blob_count, |v| v.to_le_bytes())]),
+        ];
+        let children = children.into_iter().map(|child| Box::into_raw(Box::new(child))).collect();
+        build_array(rows, Vec::new(), children)
+    }
+}
+
+/// Pack values into 8-byte aligned words, little-endian as Arrow requires
+fn words<T, const N: usize>(values: &[T], bytes: impl Fn(&T) -> [u8; N]) -> Vec<u64> {
+    let flat: Vec<u8> = values.iter().flat_map(bytes).collect();
+    flat.chunks(8).map(|chunk| {
+        let mut word = [0u8; 8];
+        word[..chunk.len()].copy_from_slice(chunk);
+        u64::from_le_bytes(word)
+    }).collect()
+}
+
+fn leaf_array(rows: usize, data: Vec<Vec<u64>>) -> ArrowArray {
+    build_array(rows, data, Vec::new())
+}
+
+/// Array with no validity bitmap (no column has nulls) followed by the `data` buffers
+fn build_array(rows: usize, data: Vec<Vec<u64>>, children: Vec<*mut ArrowArray>) -> ArrowArray {
+    let mut buffers = vec![ptr::null()];
+    buffers.extend(data.iter().map(|buffer| buffer.as_ptr() as *const c_void));
+    let mut private = Box::new(ArrayPrivate { _data: data, buffers, children });
+    ArrowArray {
+        length: rows as i64,
+        null_count: 0,
+        offset: 0,
+        n_buffers: private.buffers.len() as i64,
+        n_children: private.children.len() as i64,
+        buffers: private.buffers.as_mut_ptr(),
+        children: private.children.as_mut_ptr(),
+        dictionary: ptr::null_mut(),
+        release: Some(release_array),
+        private_data: Box::into_raw(private) as *mut c_void,
+    }
+}
+
+unsafe extern "C" fn release_array(array: *mut ArrowArray) {
+    let array = &mut *array;
+    let private = Box::from_raw(array.private_data as *mut ArrayPrivate);
+    for &child in &private.children {
+        // A consumer may have moved a child out, leaving it marked released
+        if let Some(release) = (*child).release {
+            release(child);
+        }
+        drop(Box::from_raw(child));
+    }
+    array.release = None;
+}
+
+fn schema_node(name: &'static CStr, format: &'static CStr, children: Vec<*mut ArrowSchema>) -> ArrowSchema {
+    let mut private = Box::new(SchemaPrivate { children });
+    ArrowSchema {
+        format: format.as_ptr(),
+        name: name.as_ptr(),
+        metadata: ptr::null(),
+        flags: 0, // Not nullable: every row has every column
+        n_children: private.children.len() as i64,
+        children: private.children.as_mut_ptr(),
+        dictionary: ptr::null_mut(),
+        release: Some(release_schema),
+        private_data: Box::into_raw(private) as *mut c_void,
+    }
+}
+
+/// Struct schema with one field per entry of `COLUMNS`
+fn export_schema() -> ArrowSchema {
+    let children = COLUMNS.iter()
+        .map(|&(name, format)| Box::into_raw(Box::new(schema_node(name, format, Vec::new()))))
+        .collect();
+    schema_node(c"", c"+s", children)
+}
+
+unsafe extern "C" fn release_schema(schema: *mut ArrowSchema) {
+    let schema = &mut *schema;
+    let private = Box::from_raw(schema.private_data as *mut SchemaPrivate);
+    for &child in &private.children {
+        if let Some(release) = (*child).release {
+            release(child);
+        }
+        drop(Box::from_raw(child));
+    }
+    schema.release = None;
+}
+
+unsafe extern "C" fn stream_get_schema(_stream: *mut ArrowArrayStream, out: *mut ArrowSchema) -> c_int {
+    out.write(export_schema());
+    0
+}
+
+unsafe extern "C" fn stream_get_next(stream: *mut ArrowArrayStream, out: *mut ArrowArray) -> c_int {
+    let private = &mut *((*stream).private_data as *mut StreamPrivate);
+    if private.exhausted {
+        // A released array marks the end of the stream
+        out.write(ArrowArray {
+            length: 0,
+            null_count: 0,
+            offset: 0,
+            n_buffers: 0,
+            n_children: 0,
+            buffers: ptr::null_mut(),
+            children: ptr::null_mut(),
+            dictionary: ptr::null_mut(),
+            release: None,
+            private_data: ptr::null_mut(),
+        });
+    } else {
+        out.write(private.batch.export_array());
+        private.exhausted = true;
+    }
+    0
+}
+
+unsafe extern "C" fn stream_get_last_error(_stream: *mut ArrowArrayStream) -> *const c_char {
+    ptr::null() // No call above can fail
+}
+
+unsafe extern "C" fn release_stream(stream: *mut ArrowArrayStream) {
+    let stream = &mut *stream;
+    drop(Box::from_raw(stream.private_data as *mut StreamPrivate));
+    stream.release = None;
+}
+
+#[pymethods]
+impl AnalysisBatch {
+    /// Number of rows (frames) in the batch
+    #[getter(num_rows)]
+    fn py_num_rows(&self) -> usize {
+        self.num_rows()
+    }
+
+    /// Column names in schema order
+    #[getter]
+    fn column_names(&self) -> Vec<&'static str> {
+        COLUMNS.iter().map(|(name, _)| name.to_str().unwrap_or_default()).collect()
+    }
+
+    /// `"arrow_schema"` capsule holding the struct schema of the batch
+    fn __arrow_c_schema__<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyCapsule>> {
+        schema_capsule(py)
+    }
+
+    /// `("arrow_schema", "arrow_array")` capsules holding the batch as a struct array; a
+    /// `requested_schema` is ignored, the columns are only available in the one schema
+    #[pyo3(signature = (requested_schema=None))]
+    fn __arrow_c_array__<'py>(&self, py: Python<'py>, requested_schema: Option<Bound<'py, PyAny>>) -> PyResult<(Bound<'py, PyCapsule>, Bound<'py, PyCapsule>)> {
+        let _ = requested_schema;
+        let array = PyCapsule::new_bound_with_destructor(py, self.export_array(), Some(ARRAY_CAPSULE_NAME.to_owned()), |mut array, _| {
+            if let Some(release) = array.release {
+                // SAFETY: the array was never imported, so it still owns its buffers
+                unsafe { release(&mut array) };
+            }
+        })?;
+        Ok((schema_capsule(py)?, array))
+    }
+
+    /// `"arrow_array_stream"` capsule yielding the batch once; `requested_schema` is ignored
+    #[pyo3(signature = (requested_schema=None))]
+    fn __arrow_c_stream__<'py>(&self, py: Python<'py>, requested_schema: Option<Bound<'py, PyAny>>) -> PyResult<Bound<'py, PyCapsule>> {
+        let _ = requested_schema;
+        let private = Box::new(StreamPrivate { batch: self.clone(), exhausted: false });
+        let stream = ArrowArrayStream {
+            get_schema: Some(stream_get_schema),
+            get_next: Some(stream_get_next),
+            get_last_error: Some(stream_get_last_error),
+            release: Some(release_stream),
+            private_data: Box::into_raw(private) as *mut c_void,
+        };
+        PyCapsule::new_bound_with_destructor(py, stream, Some(STREAM_CAPSULE_NAME.to_owned()), |mut stream, _| {
+            if let Some(release) = stream.release {
+                // SAFETY: the stream was never imported, so it still owns its batch
+                unsafe { release(&mut stream) };
+            }
+        })
+    }
+
+    fn __len__(&self) -> usize {
+        self.num_rows()
+    }
+
+    fn __repr__(&self) -> String {
+        format!("AnalysisBatch(num_rows={})", self.num_rows())
+    }
+}
+
+fn schema_capsule(py: Python<'_>) -> PyResult<Bound<'_, PyCapsule>> {
+    PyCapsule::new_bound_with_destructor(py, export_schema(), Some(SCHEMA_CAPSULE_NAME.to_owned()), |mut schema, _| {
+        if let Some(release) = schema.release {
+            // SAFETY: the schema was never imported, so it still owns its children
+            unsafe { release(&mut schema) };
+        }
+    })
+}
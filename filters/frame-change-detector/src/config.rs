@@ -0,0 +1,899 @@
+// THEORY:
+// The `config` module is the single source of truth for every tunable knob of the
+// frame change detector. As the detector gained options (freeze detection, output
+// coordinates, multi-channel analysis, edge triggering, decoder selection...), the
+// constructor's argument list stopped being a practical way to manage them.
+//
+// `DetectorConfig` gathers those options into one Python-visible, serde-serializable
+// value so that camera configurations can live in YAML/JSON files, be versioned, and
+// be loaded without mapping fields by hand. The detector keeps its own copy and reads
+// options from it, so adding an option means adding one field here.
+//
+// Key principles:
+// - Defaults mirror the historical constructor defaults (missing fields = old behavior)
+// - Validation happens at every entry point (Python constructor, JSON, CoreDetector::new)
+// - Unknown JSON fields are rejected so typos surface instead of being ignored
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{DetectorError, CHUNK_SIZE};
+
+/// Coordinate space for every blob/ROI coordinate reported by the detector
+#[cfg_attr(feature = "python", pyclass)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum OutputCoords {
+    PixelsAnalysis,   // Pixels of the buffer the pipeline actually analyzed
+    PixelsOriginal,   // Pixels of the decoded source frame
+    Normalized0to1,   // Fractions (0-1) of the decoded source frame
+}
+
+/// Rule for combining per-channel scene states in multi-channel mode
+#[cfg_attr(feature = "python", pyclass)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ChannelCombine {
+    Or,    // Most active channel wins: any channel Disturbed makes the scene Disturbed
+    And,   // Least active channel wins: every channel must agree before escalating
+    Max,   // Channel with the highest disturbed fraction wins
+}
+
+/// JPEG decoder used by `decode_frame`
+#[cfg_attr(feature = "python", pyclass)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum DecoderBackend {
+    Image,       // Pure-Rust `image` crate (always available)
+    TurboJpeg,   // libjpeg-turbo, decodes straight to grayscale (`turbojpeg` cargo feature)
+}
+
+/// Contrast-boosting step applied to the luma plane before analysis
+#[cfg_attr(feature = "python", pyclass)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Equalization {
+    Off,      // Analyze luma as decoded
+    Global,   // One histogram equalization over the whole frame
+    Clahe,    // Contrast-limited adaptive equalization over `clahe_tile_size` tiles
+}
+
+/// Resampling filter for every resize the detector performs
+#[cfg_attr(feature = "python", pyclass)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum ResizeFilter {
+    Nearest,      // Fastest; aliases on downscale and can create false motion
+    Triangle,     // Bilinear; good balance (default)
+    CatmullRom,   // Bicubic; sharper, slower
+    Lanczos3,     // Sharpest and cleanest, slowest
+}
+
+impl ResizeFilter {
+    /// Matching `image` crate filter
+    pub fn filter_type(self) -> image::imageops::FilterType {
+        match self {
+            ResizeFilter::Nearest => image::imageops::FilterType::Nearest,
+            ResizeFilter::Triangle => image::imageops::FilterType::Triangle,
+            ResizeFilter::CatmullRom => image::imageops::FilterType::CatmullRom,
+            ResizeFilter::Lanczos3 => image::imageops::FilterType::Lanczos3,
+        }
+    }
+}
+
+/// Which blobs `max_reported_blobs` keeps when a frame has more
+#[cfg_attr(feature = "python", pyclass)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum BlobRanking {
+    Size,   // Largest first (chunk count), then oldest
+    Age,    // Oldest first (frames tracked), then largest
+}
+
+/// Handling of near-uniform frames (lens cap, sensor glitch, white-out)
+#[cfg_attr(feature = "python", pyclass)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum UniformFramePolicy {
+    Ignore,         // Skip analysis entirely; the pipeline never sees the frame
+    TreatAsStable,  // Analyze the last good frame again and report STABLE, never triggering
+    Flag,           // Analyze as usual; only set `uniform_frame` on the result
+}
+
+/// Handling of a frame whose size differs from the one the pipeline was built for
+#[cfg_attr(feature = "python", pyclass)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum FrameSizePolicy {
+    Error,            // Raise DimensionMismatchError for any size change
+    ReinitOnRescale,  // Rebuild for the same aspect ratio at a new resolution; raise on an aspect change
+    ReinitAlways,     // Rebuild for any new size, aspect changes included
+}
+
+/// Handling of a JPEG cut off before its end-of-image marker (e.g. a dropped network transfer)
+#[cfg_attr(feature = "python", pyclass)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum TruncatedJpegPolicy {
+    Reject,  // Raise DecodeError noting the truncation
+    Pad,     // Decode the rows that arrived; the decoder fills the missing rest
+}
+
+/// Handling of a frame whose `timestamp_ms` equals the previous frame's
+#[cfg_attr(feature = "python", pyclass)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum DuplicateTimestampPolicy {
+    Process,  // Analyze it like any other frame (cooldowns see zero elapsed time)
+    Skip,     // Don't analyze it; return the previous frame's result again, never triggering
+    Error,    // Raise DuplicateTimestampError
+}
+
+/// What a freshly created pipeline does with its first frame, which has no prior to compare with
+#[cfg_attr(feature = "python", pyclass)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum FirstFramePolicy {
+    Calibrate,  // Feed it like any frame; the scene reports Calibrating until the pipeline settles
+    Reference,  // Learn it as the background first, so it reports Stable with zero disturbance
+}
+
+/// Per-chunk background the detector measures chunk change against (`background_model`)
+#[cfg_attr(feature = "python", pyclass)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum BackgroundModel {
+    Mean,      // Rolling mean of each chunk's luma over the last K frames
+    Median,    // Rolling median over the last K frames; ignores periodic motion
+    Adaptive,  // Running average that moves `background_learning_rate` (default 1/K) of the way to each new frame
+}
+
+/// What the event sink does with trigger events while its socket is disconnected
+#[cfg_attr(feature = "python", pyclass)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum EventSinkPolicy {
+    Buffer,  // Hold up to `event_sink_buffer_size` events (oldest evicted) and send them after reconnecting
+    Drop,    // Discard them; only events fired while connected are delivered
+}
+
+/// One frame preprocessing step of the `preprocessing` chain; each step's parameters stay
+/// on the option (or method) named here
+#[cfg_attr(feature = "python", pyclass)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PreprocessStep {
+    Rotate,                // `rotation`; always first, so everything downstream is upright
+    ExposureCompensation,  // `exposure_compensation`, over the full frame
+    Mask,                  // Exclusion zones (`add_exclusion_polygon`), over the full frame
+    Crop,                  // ROI (`set_roi`)
+    Upscale,               // `min_analysis_dim`
+    Subsample,             // `chunk_subsample`
+    Equalize,              // `equalization`; anywhere after Rotate
+    TemporalAverage,       // `temporal_average_frames`; always last
+}
+
+impl PreprocessStep {
+    /// Chain used when `preprocessing` is None, the order the steps always had
+    pub const DEFAULT_ORDER: [Self; 8] = [
+        Self::Rotate, Self::ExposureCompensation, Self::Mask, Self::Crop,
+        Self::Upscale, Self::Subsample, Self::Equalize, Self::TemporalAverage,
+    ];
+
+    /// Position constraint: steps of a lower stage must come first (None: any stage, which
+    /// is Equalize's rule, between Rotate and TemporalAverage)
+    fn stage(self) -> Option<u8> {
+        match self {
+            Self::Rotate => Some(0),
+            Self::ExposureCompensation | Self::Mask => Some(1), // Need the full-size frame
+            Self::Crop => Some(2),
+            Self::Upscale | Self::Subsample => Some(3),             // Resample the cropped frame
+            Self::Equalize => None,
+            Self::TemporalAverage => Some(4),
+        }
+    }
+}
+
+/// Output shaping for triggering confidence; the 0-100 raw value drives every internal
+/// decision, only the reported value is mapped
+#[cfg_attr(feature = "python", pyclass)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum ConfidenceCurve {
+    Linear {},                                // Report the raw confidence unchanged
+    Sigmoid { center: f32, steepness: f32 },  // 100 / (1 + e^(-steepness * (raw - center)))
+    Stepped { levels: Vec<f32> },             // Highest level at or below raw (ascending), else 0
+}
+
+impl ConfidenceCurve {
+    /// Map a raw 0-100 confidence through the curve
+    pub fn apply(&self, confidence: f32) -> f32 {
+        match self {
+            Self::Linear {} => confidence,
+            Self::Sigmoid { center, steepness } => 100.0 / (1.0 + (-steepness * (confidence - center)).exp()),
+            Self::Stepped { levels } => levels.iter().rev().find(|&&level| level <= confidence).copied().unwrap_or(0.0),
+        }
+    }
+}
+
+/// One entry of a `SensitivitySchedule`: a daily time window and the options it overrides
+/// while active (None keeps the detector's own value)
+#[cfg_attr(feature = "python", pyclass(get_all, set_all))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SensitivityProfile {
+    pub name: String,  // Reported by `active_profile` while the window is active
+    pub start: String, // "HH:MM" local time the window opens, inclusive
+    pub end: String,   // "HH:MM" local time the window closes, exclusive; before `start` wraps past midnight
+    #[serde(default)]
+    pub change_threshold: Option<f32>,
+    #[serde(default)]
+    pub chunk_disturbance_threshold: Option<f32>,
+    #[serde(default)]
+    pub global_min_trigger_interval_secs: Option<f64>,
+    #[serde(default)]
+    pub calm_cooldown_secs: Option<f64>,
+}
+
+impl SensitivityProfile {
+    /// (start, end) in minutes after local midnight, or None if either is not "HH:MM"
+    fn window(&self) -> Option<(u32, u32)> {
+        let minutes = |time: &str| {
+            let (hours, minutes) = time.split_once(':')?;
+            let (hours, minutes) = (hours.parse::<u32>().ok()?, minutes.parse::<u32>().ok()?);
+            (hours < 24 && minutes < 60 && time.len() == 5).then_some(hours * 60 + minutes)
+        };
+        Some((minutes(&self.start)?, minutes(&self.end)?))
+    }
+
+    /// Whether `minute` (after local midnight) falls inside the window
+    fn contains(&self, minute: u32) -> bool {
+        match self.window() {
+            Some((start, end)) if start < end => (start..end).contains(&minute),
+            Some((start, end)) => minute >= start || minute < end,
+            None => false,
+        }
+    }
+}
+
+/// Time-of-day sensitivity profiles (`sensitivity_schedule`); the first profile whose window
+/// holds the frame's local time is active, and outside every window the base options apply
+#[cfg_attr(feature = "python", pyclass(get_all, set_all))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct SensitivitySchedule {
+    pub profiles: Vec<SensitivityProfile>,
+    #[serde(default)]
+    pub utc_offset_minutes: i32, // Local time = UTC + this; fixed, so daylight saving needs a config change
+    #[serde(default)]
+    pub wall_clock: bool,        // Evaluate the system clock instead of the frame timestamp (UNIX epoch ms)
+}
+
+impl SensitivitySchedule {
+    /// Profile active at `epoch_ms` (UNIX epoch milliseconds), or None for the base options
+    pub fn active_profile(&self, epoch_ms: u64) -> Option<&SensitivityProfile> {
+        let local_minutes = (epoch_ms / 60_000) as i64 + self.utc_offset_minutes as i64;
+        let minute = local_minutes.rem_euclid(24 * 60) as u32;
+        self.profiles.iter().find(|profile| profile.contains(minute))
+    }
+}
+
+/// Clockwise rotation applied to frames before analysis (camera mounted sideways/upside down)
+#[cfg_attr(feature = "python", pyclass)]
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub enum Rotation {
+    Cw90,    // Quarter turn clockwise; width and height swap
+    Cw180,   // Half turn
+    Cw270,   // Quarter turn counter-clockwise; width and height swap
+}
+
+impl Rotation {
+    /// Matching `image` crate orientation
+    pub fn orientation(self) -> image::metadata::Orientation {
+        match self {
+            Rotation::Cw90 => image::metadata::Orientation::Rotate90,
+            Rotation::Cw180 => image::metadata::Orientation::Rotate180,
+            Rotation::Cw270 => image::metadata::Orientation::Rotate270,
+        }
+    }
+}
+
+/// Every tunable option of a `FrameChangeDetector`, loadable from and savable to JSON
+#[cfg_attr(feature = "python", pyclass(get_all, set_all))]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(default, deny_unknown_fields)]
+pub struct DetectorConfig {
+    pub buffer_duration_ms: Option<u64>,   // Accepted for compatibility, not used yet
+    pub change_threshold: f32,             // Behavioral anomaly sensitivity (percent)
+    pub frame_interval_ms: Option<u64>,    // Accepted for compatibility, not used yet
+    pub low_memory: bool,                  // Skip optional ring buffers and caches
+    pub freeze_frames: Option<u32>,        // Consecutive motionless frames before flagging a frozen feed
+    pub freeze_threshold: f32,             // Disturbed fraction at or below which a frame counts as motionless
+    pub significance_moment_window_frames: Option<u64>, // Only moments started this recently count toward the bonus
+    pub output_coords: OutputCoords,       // Coordinate space for reported blob/ROI coordinates
+    pub multi_channel: bool,               // Analyze chroma-difference planes alongside luma
+    pub channel_combine: ChannelCombine,   // How per-channel scene states are combined
+    pub edge_trigger_mode: bool,           // Trigger once per Stable->active edge instead of continuously
+    pub decoder_backend: DecoderBackend,   // Requested JPEG decoder
+    pub disturbance_hold_frames: u32,      // Frames to stay Disturbed after the fraction drops below exit
+    pub equalization: Equalization,        // Histogram equalization before analysis
+    pub clahe_tile_size: u32,              // CLAHE tile edge length in pixels
+    pub decode_cache_size: usize,          // Decoded frames memoized by content hash (0 = off)
+    pub escalate_volatile_after_frames: Option<u32>, // Consecutive Volatile frames before forcing a trigger
+    pub resize_filter: ResizeFilter,       // Resampling filter used wherever frames are resized
+    pub name: Option<String>,              // Label for log messages, repr and status (metadata only)
+    pub thumbnail_max_dim: Option<u32>,    // Attach a thumbnail fit within this size to triggering results
+    pub rotation: Option<Rotation>,        // Rotate frames upright before analysis (after EXIF orientation)
+    pub confidence_smoothing_alpha: Option<f32>, // EMA weight of the newest confidence (None = no smoothing)
+    pub strict: bool,                      // Reject options that are accepted but not honoured yet
+    pub blob_size_thresholds: (f32, f32),  // Bounding-box share of the frame: below .0 is small, at/above .1 large
+    pub deterministic: bool,               // Frame-timestamp cooldown clock and stable blob ids/order, for replays
+    pub disturbance_spike_threshold: Option<f32>, // Single-frame instability that promotes Volatile to Disturbed at once
+    pub max_reported_blobs: Option<usize>, // Report at most this many blobs per frame (None = all)
+    pub blob_ranking: BlobRanking,         // Which blobs are kept when truncating
+    pub luma_coefficients: (f32, f32, f32), // R, G, B weights for `process_rgb` luma conversion
+    pub error_if_calibrating_after: Option<u64>, // Raise once the pipeline is still Calibrating after this many frames
+    pub adaptive_cooldown: bool,           // Shorten the Disturbed cooldown for high-confidence events
+    pub adaptive_cooldown_factor: f32,     // Fraction of the cooldown a 100-confidence event skips (0-1)
+    pub adaptive_cooldown_min_ms: u64,     // Floor for the shortened Disturbed cooldown
+    pub profile: bool,                     // Record per-stage timings on each result (small overhead)
+    pub uniform_frame_policy: UniformFramePolicy, // What to do with near-uniform (black/white-out) frames
+    pub uniform_variance_threshold: f32,   // Luma variance below which a frame counts as uniform
+    pub chunk_disturbance_threshold: Option<f32>, // Mean-luma change a chunk needs to count as moving (None = pipeline's own 2.0 rule)
+    pub require_significant_moment: bool,  // Trigger only on frames whose report carries a significant moment
+    pub record_batch_results: Option<usize>, // Keep this many latest batch results for get_recorded_analysis (None = off)
+    pub temporal_average_frames: Option<usize>, // Average the last K analyzed frames before the pipeline (temporal denoise)
+    pub poison_on_panic: bool,             // After a pipeline panic, refuse frames until reset()
+    pub min_analysis_dim: Option<u32>,     // Upscale frames whose shorter side is below this before analysis
+    pub disturbed_base_confidence: f32,    // Confidence of a Disturbed trigger before the significance bonus
+    pub calibration_feedback: bool,        // Report disturbed_fraction from the detector's own chunk deltas while Calibrating
+    pub frame_size_policy: FrameSizePolicy, // What to do when the frame size changes mid-stream
+    pub global_min_trigger_interval_secs: Option<f64>, // Minimum seconds between any two triggers, whatever their path
+    pub blob_confidence_weight: f32,       // Confidence points a Disturbed trigger gains at full blob coverage
+    pub seed: Option<u64>,                 // Reserved RNG seed; nothing in the detector is random yet
+    pub trigger_on_calm: bool,             // Set `calmed` whenever activity returns to Stable, in any mode
+    pub calm_cooldown_secs: f64,           // Minimum seconds between two `calmed` signals with trigger_on_calm
+    pub chunk_subsample: u32,              // Analyze only every Nth chunk row and column (1 = every chunk)
+    pub truncated_jpeg_policy: TruncatedJpegPolicy, // What to do with a JPEG missing its end-of-image marker
+    pub duplicate_timestamp_policy: DuplicateTimestampPolicy, // What to do with a frame repeating the previous frame's timestamp
+    pub edge_trigger_confidence: Option<f32>, // Fixed confidence of edge triggers (None = Disturbed formula)
+    pub max_frame_dimension: Option<u32>,  // Largest accepted frame width or height, checked before decoding (None = no limit)
+    pub alert_on_first_motion: bool,       // One-shot trigger on the first Disturbed frame after calibration, past every gate
+    pub exposure_compensation: bool,       // Shift each frame mean luma back to the calibration reference (auto-exposure)
+    pub first_frame_policy: FirstFramePolicy, // How a freshly created pipeline treats its first frame
+    pub tile_size: Option<(u32, u32)>,     // Split the analysis frame into (width, height) tiles, each with its own pipeline
+    pub trigger_queue_size: Option<usize>, // Queue up to this many fired triggers for drain_triggers (None = off)
+    pub confidence_curve: ConfidenceCurve, // Reshape triggering confidence before it is returned
+    pub background_model: Option<BackgroundModel>, // Compare chunks against this background instead of the previous frame
+    pub background_model_frames: u32,      // K: frames in the Mean/Median window, or the Adaptive time constant
+    pub background_learning_rate: Option<f32>, // Share of each frame the Adaptive background takes in, 0-1 (None = 1/K)
+    pub watchdog_timeout_ms: Option<u64>,  // Run the pipeline on a worker thread and give up after this many ms (None = inline)
+    pub new_moment_weight: f32,            // Confidence points a Disturbed trigger gains per new significant moment
+    pub completed_moment_weight: f32,      // Confidence points a Disturbed trigger gains per completed significant moment
+    pub sensitivity_schedule: Option<SensitivitySchedule>, // Time-of-day threshold/cooldown overrides (None = the options above all day)
+    pub preprocessing: Option<Vec<PreprocessStep>>, // Order of the enabled preprocessing steps (None = PreprocessStep::DEFAULT_ORDER)
+    pub event_sink_policy: EventSinkPolicy, // Event sink: what happens to events while disconnected
+    pub event_sink_buffer_size: usize,     // EventSinkPolicy::Buffer: hold at most this many events
+}
+
+impl Default for DetectorConfig {
+    fn default() -> Self {
+        Self {
+            buffer_duration_ms: None,
+            change_threshold: 5.0,
+            frame_interval_ms: None,
+            low_memory: false,
+            freeze_frames: None,
+            freeze_threshold: 0.0,
+            significance_moment_window_frames: None,
+            output_coords: OutputCoords::PixelsAnalysis,
+            multi_channel: false,
+            channel_combine: ChannelCombine::Or,
+            edge_trigger_mode: false,
+            decoder_backend: DecoderBackend::Image,
+            disturbance_hold_frames: 0,
+            equalization: Equalization::Off,
+            clahe_tile_size: 64,
+            decode_cache_size: 0,
+            escalate_volatile_after_frames: None,
+            resize_filter: ResizeFilter::Triangle,
+            name: None,
+            thumbnail_max_dim: None,
+            rotation: None,
+            confidence_smoothing_alpha: None,
+            strict: false,
+            blob_size_thresholds: (0.01, 0.1),
+            deterministic: false,
+            disturbance_spike_threshold: None,
+            max_reported_blobs: None,
+            blob_ranking: BlobRanking::Size,
+            luma_coefficients: (0.2126, 0.7152, 0.0722),
+            error_if_calibrating_after: None,
+            adaptive_cooldown: false,
+            adaptive_cooldown_factor: 0.5,
+            adaptive_cooldown_min_ms: 50,
+            profile: false,
+            uniform_frame_policy: UniformFramePolicy::Flag,
+            uniform_variance_threshold: 4.0,
+            chunk_disturbance_threshold: None,
+            require_significant_moment: false,
+            record_batch_results: None,
+            temporal_average_frames: None,
+            poison_on_panic: false,
+            min_analysis_dim: None,
+            disturbed_base_confidence: 95.0,
+            calibration_feedback: false,
+            frame_size_policy: FrameSizePolicy::Error,
+            global_min_trigger_interval_secs: None,
+            blob_confidence_weight: 0.0,
+            seed: None,
+            trigger_on_calm: false,
+            calm_cooldown_secs: 1.0,
+            chunk_subsample: 1,
+            truncated_jpeg_policy: TruncatedJpegPolicy::Pad,
+            duplicate_timestamp_policy: DuplicateTimestampPolicy::Process,
+            edge_trigger_confidence: None,
+            max_frame_dimension: Some(16384),
+            alert_on_first_motion: false,
+            exposure_compensation: false,
+            first_frame_policy: FirstFramePolicy::Calibrate,
+            tile_size: None,
+            trigger_queue_size: None,
+            confidence_curve: ConfidenceCurve::Linear {},
+            background_model: None,
+            background_model_frames: 20,
+            background_learning_rate: None,
+            watchdog_timeout_ms: None,
+            new_moment_weight: 5.0,
+            completed_moment_weight: 5.0,
+            sensitivity_schedule: None,
+            preprocessing: None,
+            event_sink_policy: EventSinkPolicy::Buffer,
+            event_sink_buffer_size: 256,
+        }
+    }
+}
+
+impl DetectorConfig {
+    /// Parse a JSON object; missing fields take their defaults, unknown fields are rejected
+    pub fn from_json(json: &str) -> Result<Self, DetectorError> {
+        let config: Self = serde_json::from_str(json)
+            .map_err(|e| DetectorError::Config(format!("Invalid detector config JSON: {}", e)))?;
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Serialize every field to a JSON object
+    pub fn to_json(&self) -> Result<String, DetectorError> {
+        serde_json::to_string(self)
+            .map_err(|e| DetectorError::Serialize(format!("Could not serialize detector config: {}", e)))
+    }
+
+    /// `DetectorError::Config` if any value is out of range (or, in strict mode, would be
+    /// ignored); the message lists every problem found by `validation_errors`
+    pub fn validate(&self) -> Result<(), DetectorError> {
+        let errors = self.validation_errors();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(DetectorError::Config(errors.join("; ")))
+        }
+    }
+
+    /// Every out-of-range value (and, in strict mode, every option that would be ignored),
+    /// one message each in field order; empty when the config is valid
+    pub fn validation_errors(&self) -> Vec<String> {
+        let mut errors = Vec::new();
+        if self.strict {
+            let ignored = [
+                ("buffer_duration_ms", self.buffer_duration_ms.is_some()),
+                ("frame_interval_ms", self.frame_interval_ms.is_some()),
+                ("seed", self.seed.is_some()),
+            ];
+            for (option, _) in ignored.iter().filter(|(_, set)| *set) {
+                errors.push(format!("{} is not supported yet and would be ignored (strict mode)", option));
+            }
+        }
+        if !(0.0..=1.0).contains(&self.freeze_threshold) {
+            errors.push(format!("freeze_threshold must be within 0-1, got {}", self.freeze_threshold));
+        }
+        if self.freeze_frames == Some(0) {
+            errors.push("freeze_frames must be at least 1 (use None to disable)".into());
+        }
+        if !self.change_threshold.is_finite() || self.change_threshold < 0.0 {
+            errors.push(format!("change_threshold must be a non-negative number, got {}", self.change_threshold));
+        }
+        if self.escalate_volatile_after_frames == Some(0) {
+            errors.push("escalate_volatile_after_frames must be at least 1 (use None to disable)".into());
+        }
+        if self.thumbnail_max_dim == Some(0) {
+            errors.push("thumbnail_max_dim must be at least 1 (use None to disable)".into());
+        }
+        if let Some(alpha) = self.confidence_smoothing_alpha {
+            if !(alpha > 0.0 && alpha <= 1.0) {
+                errors.push(format!("confidence_smoothing_alpha must be within (0, 1], got {}", alpha));
+            }
+        }
+        if let Some(spike) = self.disturbance_spike_threshold {
+            if !(0.0..=1.0).contains(&spike) {
+                errors.push(format!("disturbance_spike_threshold must be within 0-1, got {}", spike));
+            }
+        }
+        let (small_below, large_from) = self.blob_size_thresholds;
+        if !(0.0 < small_below && small_below <= large_from && large_from <= 1.0) {
+            errors.push(format!(
+                "blob_size_thresholds must satisfy 0 < small <= large <= 1, got ({}, {})", small_below, large_from
+            ));
+        }
+        let (r, g, b) = self.luma_coefficients;
+        if ![r, g, b].iter().all(|weight| weight.is_finite() && *weight >= 0.0) || r + g + b <= 0.0 {
+            errors.push(format!(
+                "luma_coefficients must be non-negative and not all zero, got ({}, {}, {})", r, g, b
+            ));
+        }
+        if !(0.0..=1.0).contains(&self.adaptive_cooldown_factor) {
+            errors.push(format!(
+                "adaptive_cooldown_factor must be within 0-1, got {}", self.adaptive_cooldown_factor
+            ));
+        }
+        if !(self.uniform_variance_threshold.is_finite() && self.uniform_variance_threshold >= 0.0) {
+            errors.push(format!(
+                "uniform_variance_threshold must be a non-negative number, got {}", self.uniform_variance_threshold
+            ));
+        }
+        if let Some(schedule) = &self.sensitivity_schedule {
+            self.schedule_errors(schedule, &mut errors);
+        }
+        if let Some(threshold) = self.chunk_disturbance_threshold {
+            if !(threshold.is_finite() && threshold >= 0.0) {
+                errors.push(format!("chunk_disturbance_threshold must be a non-negative number, got {}", threshold));
+            }
+        }
+        match &self.confidence_curve {
+            ConfidenceCurve::Linear {} => {}
+            ConfidenceCurve::Sigmoid { center, steepness } => {
+                if !(center.is_finite() && steepness.is_finite() && *steepness > 0.0) {
+                    errors.push(format!(
+                        "confidence_curve Sigmoid needs a finite center and a positive steepness, got center={} steepness={}",
+                        center, steepness
+                    ));
+                }
+            }
+            ConfidenceCurve::Stepped { levels } => {
+                let in_range = levels.iter().all(|level| (0.0..=100.0).contains(level));
+                if levels.is_empty() || !in_range || levels.windows(2).any(|pair| pair[0] >= pair[1]) {
+                    errors.push(format!(
+                        "confidence_curve Stepped needs ascending levels within 0-100, got {:?}", levels
+                    ));
+                }
+            }
+        }
+        if let Some(rate) = self.background_learning_rate {
+            if !(0.0..=1.0).contains(&rate) {
+                errors.push(format!("background_learning_rate must be within 0-1, got {}", rate));
+            }
+        }
+        if self.background_model_frames == 0 {
+            errors.push("background_model_frames must be at least 1".into());
+        }
+        if self.watchdog_timeout_ms == Some(0) {
+            errors.push("watchdog_timeout_ms must be at least 1 (use None to disable)".into());
+        }
+        if self.event_sink_policy == EventSinkPolicy::Buffer && self.event_sink_buffer_size == 0 {
+            errors.push("event_sink_buffer_size must be at least 1 with EventSinkPolicy.Buffer (use EventSinkPolicy.Drop instead)".into());
+        }
+        if self.trigger_queue_size == Some(0) {
+            errors.push("trigger_queue_size must be at least 1 (use None to disable)".into());
+        }
+        if self.record_batch_results == Some(0) {
+            errors.push("record_batch_results must be at least 1 (use None to disable)".into());
+        }
+        if self.temporal_average_frames == Some(0) {
+            errors.push("temporal_average_frames must be at least 1 (use None to disable)".into());
+        }
+        if let Some(confidence) = self.edge_trigger_confidence {
+            if !(0.0..=100.0).contains(&confidence) {
+                errors.push(format!("edge_trigger_confidence must be within [0, 100], got {}", confidence));
+            }
+        }
+        if !(0.0..=100.0).contains(&self.disturbed_base_confidence) {
+            errors.push(format!("disturbed_base_confidence must be within [0, 100], got {}", self.disturbed_base_confidence));
+        }
+        if let Some(interval) = self.global_min_trigger_interval_secs {
+            if !(interval.is_finite() && interval >= 0.0) {
+                errors.push(format!("global_min_trigger_interval_secs must be a non-negative number of seconds, got {}", interval));
+            }
+        }
+        for (name, weight) in [("new_moment_weight", self.new_moment_weight), ("completed_moment_weight", self.completed_moment_weight)] {
+            if !(weight.is_finite() && weight >= 0.0) {
+                errors.push(format!("{} must be a non-negative number, got {}", name, weight));
+            }
+        }
+        if !(self.blob_confidence_weight.is_finite() && self.blob_confidence_weight >= 0.0) {
+            errors.push(format!("blob_confidence_weight must be a non-negative number, got {}", self.blob_confidence_weight));
+        }
+        if !(self.calm_cooldown_secs.is_finite() && self.calm_cooldown_secs >= 0.0) {
+            errors.push(format!("calm_cooldown_secs must be a non-negative number of seconds, got {}", self.calm_cooldown_secs));
+        }
+        if self.chunk_subsample == 0 {
+            errors.push("chunk_subsample must be at least 1 (1 analyzes every chunk)".into());
+        }
+        if self.max_frame_dimension == Some(0) {
+            errors.push("max_frame_dimension must be at least 1 pixel (use None to disable)".into());
+        }
+        if self.min_analysis_dim == Some(0) {
+            errors.push("min_analysis_dim must be at least 1 pixel (use None to disable)".into());
+        }
+        if let Some((width, height)) = self.tile_size {
+            // Tiles must cover whole chunks so the per-tile grids stitch into the frame grid
+            if width == 0 || height == 0 || !width.is_multiple_of(CHUNK_SIZE) || !height.is_multiple_of(CHUNK_SIZE) {
+                errors.push(format!(
+                    "tile_size must be a non-zero multiple of the {}-pixel chunk size in both dimensions, got {}x{}",
+                    CHUNK_SIZE, width, height
+                ));
+            }
+        }
+        if self.clahe_tile_size == 0 {
+            errors.push("clahe_tile_size must be at least 1 pixel".into());
+        }
+        if let Some(steps) = &self.preprocessing {
+            self.preprocessing_errors(steps, &mut errors);
+        }
+        errors
+    }
+
+    /// Preprocessing steps in the order they run: `preprocessing`, or the default order
+    pub fn preprocessing_steps(&self) -> Vec<PreprocessStep> {
+        self.preprocessing.clone().unwrap_or_else(|| PreprocessStep::DEFAULT_ORDER.to_vec())
+    }
+
+    /// Each step at most once, in an order that keeps the frame geometry consistent, and
+    /// every step whose option is set must be listed
+    fn preprocessing_errors(&self, steps: &[PreprocessStep], errors: &mut Vec<String>) {
+        for (index, step) in steps.iter().enumerate() {
+            if steps[..index].contains(step) {
+                errors.push(format!("preprocessing lists {:?} more than once", step));
+            }
+        }
+        let mut stage = 0;
+        for (index, &step) in steps.iter().enumerate() {
+            let misplaced = match step.stage() {
+                Some(step_stage) => {
+                    let misplaced = step_stage < stage;
+                    stage = stage.max(step_stage);
+                    misplaced
+                }
+                None => steps[..index].contains(&PreprocessStep::TemporalAverage) || steps[index..].contains(&PreprocessStep::Rotate),
+            };
+            if misplaced {
+                errors.push(format!(
+                    "preprocessing cannot run {:?} at position {}: Rotate comes first, ExposureCompensation and Mask \
+                     before Crop, Crop before Upscale and Subsample, and TemporalAverage last; got {:?}",
+                    step, index + 1, steps
+                ));
+            }
+        }
+        let enabled = [
+            (PreprocessStep::Rotate, "rotation", self.rotation.is_some()),
+            (PreprocessStep::ExposureCompensation, "exposure_compensation", self.exposure_compensation),
+            (PreprocessStep::Upscale, "min_analysis_dim", self.min_analysis_dim.is_some()),
+            (PreprocessStep::Subsample, "chunk_subsample", self.chunk_subsample > 1),
+            (PreprocessStep::Equalize, "equalization", self.equalization != Equalization::Off),
+            (PreprocessStep::TemporalAverage, "temporal_average_frames", self.temporal_average_frames.is_some()),
+        ];
+        for (step, option, _) in enabled.iter().filter(|(step, _, set)| *set && !steps.contains(step)) {
+            errors.push(format!("{} is set but preprocessing leaves out {:?}, so it would be ignored", option, step));
+        }
+    }
+
+    /// These options with `profile`'s overrides applied (unchanged for None)
+    pub fn with_profile(&self, profile: Option<&SensitivityProfile>) -> Self {
+        let mut config = self.clone();
+        if let Some(profile) = profile {
+            config.change_threshold = profile.change_threshold.unwrap_or(config.change_threshold);
+            config.chunk_disturbance_threshold = profile.chunk_disturbance_threshold.or(config.chunk_disturbance_threshold);
+            config.global_min_trigger_interval_secs = profile.global_min_trigger_interval_secs.or(config.global_min_trigger_interval_secs);
+            config.calm_cooldown_secs = profile.calm_cooldown_secs.unwrap_or(config.calm_cooldown_secs);
+        }
+        config
+    }
+
+    /// Windows must parse and names be unique; each profile's overrides are range-checked
+    /// like the options they replace
+    fn schedule_errors(&self, schedule: &SensitivitySchedule, errors: &mut Vec<String>) {
+        if schedule.utc_offset_minutes.abs() > 14 * 60 {
+            errors.push(format!(
+                "sensitivity_schedule utc_offset_minutes must be within +-840 (14 hours), got {}", schedule.utc_offset_minutes
+            ));
+        }
+        for (index, profile) in schedule.profiles.iter().enumerate() {
+            if profile.name.is_empty() || schedule.profiles[..index].iter().any(|other| other.name == profile.name) {
+                errors.push(format!("sensitivity_schedule profile names must be non-empty and unique, got {:?}", profile.name));
+            }
+            if profile.window().is_none_or(|(start, end)| start == end) {
+                errors.push(format!(
+                    "sensitivity_schedule profile '{}' needs distinct \"HH:MM\" start and end times, got {:?}-{:?}",
+                    profile.name, profile.start, profile.end
+                ));
+            }
+            let mut effective = self.with_profile(Some(profile));
+            effective.sensitivity_schedule = None;
+            // Only report the options this profile sets; the rest were checked above
+            let overridden: Vec<&str> = [
+                ("change_threshold", profile.change_threshold.is_some()),
+                ("chunk_disturbance_threshold", profile.chunk_disturbance_threshold.is_some()),
+                ("global_min_trigger_interval_secs", profile.global_min_trigger_interval_secs.is_some()),
+                ("calm_cooldown_secs", profile.calm_cooldown_secs.is_some()),
+            ].into_iter().filter(|(_, set)| *set).map(|(option, _)| option).collect();
+            errors.extend(effective.validation_errors().into_iter()
+                .filter(|error| overridden.iter().any(|option| error.starts_with(option)))
+                .map(|error| format!("sensitivity_schedule profile '{}': {}", profile.name, error)));
+        }
+    }
+}
+
+#[cfg(feature = "python")]
+#[pymethods]
+impl DetectorConfig {
+    #[new]
+    #[pyo3(signature = (buffer_duration_ms=None, change_threshold=5.0, frame_interval_ms=None, low_memory=false, freeze_frames=None, freeze_threshold=0.0, significance_moment_window_frames=None, output_coords=OutputCoords::PixelsAnalysis, multi_channel=false, channel_combine=ChannelCombine::Or, edge_trigger_mode=false, decoder_backend=DecoderBackend::Image, disturbance_hold_frames=0, equalization=Equalization::Off, clahe_tile_size=64, decode_cache_size=0, escalate_volatile_after_frames=None, resize_filter=ResizeFilter::Triangle, name=None, thumbnail_max_dim=None, rotation=None, confidence_smoothing_alpha=None, strict=false, blob_size_thresholds=(0.01, 0.1), deterministic=false, disturbance_spike_threshold=None, max_reported_blobs=None, blob_ranking=BlobRanking::Size, luma_coefficients=(0.2126, 0.7152, 0.0722), error_if_calibrating_after=None, adaptive_cooldown=false, adaptive_cooldown_factor=0.5, adaptive_cooldown_min_ms=50, profile=false, uniform_frame_policy=UniformFramePolicy::Flag, uniform_variance_threshold=4.0, chunk_disturbance_threshold=None, require_significant_moment=false, record_batch_results=None, temporal_average_frames=None, poison_on_panic=false, min_analysis_dim=None, disturbed_base_confidence=95.0, calibration_feedback=false, frame_size_policy=FrameSizePolicy::Error, global_min_trigger_interval_secs=None, blob_confidence_weight=0.0, seed=None, trigger_on_calm=false, calm_cooldown_secs=1.0, chunk_subsample=1, truncated_jpeg_policy=TruncatedJpegPolicy::Pad, duplicate_timestamp_policy=DuplicateTimestampPolicy::Process, edge_trigger_confidence=None, max_frame_dimension=Some(16384), alert_on_first_motion=false, exposure_compensation=false, first_frame_policy=FirstFramePolicy::Calibrate, tile_size=None, trigger_queue_size=None, confidence_curve=ConfidenceCurve::Linear {}, background_model=None, background_model_frames=20, background_learning_rate=None, watchdog_timeout_ms=None, new_moment_weight=5.0, completed_moment_weight=5.0, sensitivity_schedule=None, preprocessing=None, event_sink_policy=EventSinkPolicy::Buffer, event_sink_buffer_size=256))]
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        buffer_duration_ms: Option<u64>,
+        change_threshold: f32,
+        frame_interval_ms: Option<u64>,
+        low_memory: bool,
+        freeze_frames: Option<u32>,
+        freeze_threshold: f32,
+        significance_moment_window_frames: Option<u64>,
+        output_coords: OutputCoords,
+        multi_channel: bool,
+        channel_combine: ChannelCombine,
+        edge_trigger_mode: bool,
+        decoder_backend: DecoderBackend,
+        disturbance_hold_frames: u32,
+        equalization: Equalization,
+        clahe_tile_size: u32,
+        decode_cache_size: usize,
+        escalate_volatile_after_frames: Option<u32>,
+        resize_filter: ResizeFilter,
+        name: Option<String>,
+        thumbnail_max_dim: Option<u32>,
+        rotation: Option<Rotation>,
+        confidence_smoothing_alpha: Option<f32>,
+        strict: bool,
+        blob_size_thresholds: (f32, f32),
+        deterministic: bool,
+        disturbance_spike_threshold: Option<f32>,
+        max_reported_blobs: Option<usize>,
+        blob_ranking: BlobRanking,
+        luma_coefficients: (f32, f32, f32),
+        error_if_calibrating_after: Option<u64>,
+        adaptive_cooldown: bool,
+        adaptive_cooldown_factor: f32,
+        adaptive_cooldown_min_ms: u64,
+        profile: bool,
+        uniform_frame_policy: UniformFramePolicy,
+        uniform_variance_threshold: f32,
+        chunk_disturbance_threshold: Option<f32>,
+        require_significant_moment: bool,
+        record_batch_results: Option<usize>,
+        temporal_average_frames: Option<usize>,
+        poison_on_panic: bool,
+        min_analysis_dim: Option<u32>,
+        disturbed_base_confidence: f32,
+        calibration_feedback: bool,
+        frame_size_policy: FrameSizePolicy,
+        global_min_trigger_interval_secs: Option<f64>,
+        blob_confidence_weight: f32,
+        seed: Option<u64>,
+        trigger_on_calm: bool,
+        calm_cooldown_secs: f64,
+        chunk_subsample: u32,
+        truncated_jpeg_policy: TruncatedJpegPolicy,
+        duplicate_timestamp_policy: DuplicateTimestampPolicy,
+        edge_trigger_confidence: Option<f32>,
+        max_frame_dimension: Option<u32>,
+        alert_on_first_motion: bool,
+        exposure_compensation: bool,
+        first_frame_policy: FirstFramePolicy,
+        tile_size: Option<(u32, u32)>,
+        trigger_queue_size: Option<usize>,
+        confidence_curve: ConfidenceCurve,
+        background_model: Option<BackgroundModel>,
+        background_model_frames: u32,
+        background_learning_rate: Option<f32>,
+        watchdog_timeout_ms: Option<u64>,
+        new_moment_weight: f32,
+        completed_moment_weight: f32,
+        sensitivity_schedule: Option<SensitivitySchedule>,
+        preprocessing: Option<Vec<PreprocessStep>>,
+        event_sink_policy: EventSinkPolicy,
+        event_sink_buffer_size: usize,
+    ) -> PyResult<Self> {
+        let config = Self {
+            buffer_duration_ms,
+            change_threshold,
+            frame_interval_ms,
+            low_memory,
+            freeze_frames,
+            freeze_threshold,
+            significance_moment_window_frames,
+            output_coords,
+            multi_channel,
+            channel_combine,
+            edge_trigger_mode,
+            decoder_backend,
+            disturbance_hold_frames,
+            equalization,
+            clahe_tile_size,
+            decode_cache_size,
+            escalate_volatile_after_frames,
+            resize_filter,
+            name,
+            thumbnail_max_dim,
+            rotation,
+            confidence_smoothing_alpha,
+            strict,
+            blob_size_thresholds,
+            deterministic,
+            disturbance_spike_threshold,
+            max_reported_blobs,
+            blob_ranking,
+            luma_coefficients,
+            error_if_calibrating_after,
+            adaptive_cooldown,
+            adaptive_cooldown_factor,
+            adaptive_cooldown_min_ms,
+            profile,
+            uniform_frame_policy,
+            uniform_variance_threshold,
+            chunk_disturbance_threshold,
+            require_significant_moment,
+            record_batch_results,
+            temporal_average_frames,
+            poison_on_panic,
+            min_analysis_dim,
+            disturbed_base_confidence,
+            calibration_feedback,
+            frame_size_policy,
+            global_min_trigger_interval_secs,
+            blob_confidence_weight,
+            seed,
+            trigger_on_calm,
+            calm_cooldown_secs,
+            chunk_subsample,
+            truncated_jpeg_policy,
+            duplicate_timestamp_policy,
+            edge_trigger_confidence,
+            max_frame_dimension,
+            alert_on_first_motion,
+            exposure_compensation,
+            first_frame_policy,
+            tile_size,
+            trigger_queue_size,
+            confidence_curve,
+            background_model,
+            background_model_frames,
+            background_learning_rate,
+            watchdog_timeout_ms,
+            new_moment_weight,
+            completed_moment_weight,
+            sensitivity_schedule,
+            preprocessing,
+            event_sink_policy,
+            event_sink_buffer_size,
+        };
+        config.validate()?;
+        Ok(config)
+    }
+
+    /// Parse a JSON object; missing fields take their defaults, unknown fields are rejected
+    #[staticmethod]
+    #[pyo3(name = "from_json")]
+    fn py_from_json(json: &str) -> PyResult<Self> {
+        Ok(Self::from_json(json)?)
+    }
+
+    /// Serialize every field to a JSON object
+    #[pyo3(name = "to_json")]
+    fn py_to_json(&self) -> PyResult<String> {
+        Ok(self.to_json()?)
+    }
+
+    /// Raise `ConfigError` if any value is out of range (or, in strict mode, would be ignored)
+    #[pyo3(name = "validate")]
+    fn py_validate(&self) -> PyResult<()> {
+        Ok(self.validate()?)
+    }
+
+    fn __repr__(&self) -> String {
+        format!("DetectorConfig({})", self.to_json().unwrap_or_default())
+    }
+}
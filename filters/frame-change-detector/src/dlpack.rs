@@ -0,0 +1,168 @@
+// THEORY:
+// The `dlpack` module feeds DLPack tensors to the detector (`dlpack` cargo feature, which
+// implies `python`). GPU capture and decode pipelines hand frames around as DLPack
+// tensors, but the detector's analysis is CPU-only: a tensor is copied into a host buffer
+// once and then goes down the raw pixel path like `process_buffer`. Host-accessible
+// tensors (CPU, pinned or managed memory) are read directly; for device memory the
+// producer is asked to do the download itself through the
+// `__dlpack__(dl_device=..., copy=True)` protocol, which keeps this crate free of any
+// CUDA/ROCm/Vulkan runtime dependency.
+//
+// Capsule ownership follows the DLPack Python spec: a consumer that takes the tensor
+// renames the capsule to "used_dltensor" and calls the deleter when done. A capsule that
+// is rejected is left untouched, so its own destructor still frees it.
+
+use std::ffi::{c_void, CStr};
+
+use pyo3::exceptions::PyTypeError;
+use pyo3::prelude::*;
+use pyo3::types::{IntoPyDict, PyCapsule};
+
+use crate::python::{DecodeError, DimensionMismatchError};
+
+const DLPACK_CAPSULE_NAME: &CStr = c"dltensor";
+const DLPACK_USED_CAPSULE_NAME: &CStr = c"used_dltensor";
+
+// `DLDeviceType` values whose memory the CPU can read directly
+const DL_CPU: i32 = 1;
+const DL_CUDA_HOST: i32 = 3;
+const DL_ROCM_HOST: i32 = 11;
+const DL_CUDA_MANAGED: i32 = 13;
+
+const DL_UINT: u8 = 1; // `DLDataTypeCode::kDLUInt`
+
+#[repr(C)]
+struct DLDevice {
+    device_type: i32,
+    device_id: i32,
+}
+
+#[repr(C)]
+struct DLDataType {
+    code: u8,
+    bits: u8,
+    lanes: u16,
+}
+
+#[repr(C)]
+struct DLTensor {
+    data: *mut c_void,
+    device: DLDevice,
+    ndim: i32,
+    dtype: DLDataType,
+    shape: *mut i64,
+    strides: *mut i64, // In elements; null means compact row-major
+    byte_offset: u64,
+}
+
+#[repr(C)]
+struct DLManagedTensor {
+    dl_tensor: DLTensor,
+    manager_ctx: *mut c_void,
+    deleter: Option<unsafe extern "C" fn(*mut DLManagedTensor)>,
+}
+
+fn host_accessible(device_type: i32) -> bool {
+    matches!(device_type, DL_CPU | DL_CUDA_HOST | DL_ROCM_HOST | DL_CUDA_MANAGED)
+}
+
+/// Copy an 8-bit single-channel DLPack tensor (a "dltensor" capsule or any object with
+/// `__dlpack__`) into a row-major host plane: `(pixels, width, height)`
+pub(crate) fn host_luma(tensor: &Bound<'_, PyAny>) -> PyResult<(Vec<u8>, u32, u32)> {
+    let py = tensor.py();
+    let capsule = if let Ok(capsule) = tensor.downcast::<PyCapsule>() {
+        capsule.clone()
+    } else if tensor.hasattr("__dlpack__")? {
+        // Producers without `__dlpack_device__` are assumed to be on the host
+        let device_type = match tensor.call_method0("__dlpack_device__") {
+            Ok(device) => device.extract::<(i32, i32)>()?.0,
+            Err(_) => DL_CPU,
+        };
+        let exported = if host_accessible(device_type) {
+            tensor.call_method0("__dlpack__")?
+        } else {
+            let kwargs = [("dl_device", (DL_CPU, 0).into_py(py)), ("copy", true.into_py(py))].into_py_dict_bound(py);
+            tensor.call_method("__dlpack__", (), Some(&kwargs)).map_err(|e| DecodeError::new_err(format!(
+                "DLPack tensor on device type {} could not be copied to host memory by its producer ({}); \
+                 move it to the host first (e.g. tensor.cpu())", device_type, e
+            )))?
+        };
+        exported.downcast_into::<PyCapsule>()?
+    } else {
+        return Err(PyTypeError::new_err(format!(
+            "Expected a DLPack capsule or an object with __dlpack__, got {}", tensor.get_type().name()?
+        )));
+    };
+    if capsule.name()? != Some(DLPACK_CAPSULE_NAME) {
+        return Err(PyTypeError::new_err("DLPack capsule was already consumed or is not a \"dltensor\" capsule"));
+    }
+
+    let managed = capsule.pointer() as *mut DLManagedTensor;
+    // SAFETY: a live "dltensor" capsule points at a DLManagedTensor owned by its producer
+    // until the capsule is consumed; only its fields are read here
+    let (pixels, width, height) = unsafe { copy_plane(&(*managed).dl_tensor)? };
+
+    // Take ownership only after the copy succeeded, then release the producer's memory
+    // SAFETY: renaming marks the capsule consumed, so its destructor no longer deletes the
+    // tensor; the deleter is called exactly once, here
+    unsafe {
+        if pyo3::ffi::PyCapsule_SetName(capsule.as_ptr(), DLPACK_USED_CAPSULE_NAME.as_ptr()) != 0 {
+            return Err(PyErr::fetch(py));
+        }
+        if let Some(deleter) = (*managed).deleter {
+            deleter(managed);
+        }
+    }
+    Ok((pixels, width, height))
+}
+
+/// Validate the tensor layout (uint8, `(height, width)` or `(height, width, 1)`, host
+/// memory) and gather it into a row-major plane
+///
+/// # Safety
+/// `tensor` must describe live memory matching its shape and strides.
+unsafe fn copy_plane(tensor: &DLTensor) -> PyResult<(Vec<u8>, u32, u32)> {
+    if !host_accessible(tensor.device.device_type) {
+        return Err(DecodeError::new_err(format!(
+            "DLPack tensor is on device type {} (id {}), which the CPU cannot read; pass the tensor \
+             object itself so its producer can copy it, or move it to the host first",
+            tensor.device.device_type, tensor.device.device_id
+        )));
+    }
+    let dtype = &tensor.dtype;
+    if (dtype.code, dtype.bits, dtype.lanes) != (DL_UINT, 8, 1) {
+        return Err(DecodeError::new_err(format!(
+            "DLPack tensor must be uint8, got type code {} with {} bits x {} lanes", dtype.code, dtype.bits, dtype.lanes
+        )));
+    }
+    let ndim = tensor.ndim.max(0) as usize;
+    let shape = std::slice::from_raw_parts(tensor.shape, ndim);
+    if !(ndim == 2 || ndim == 3 && shape[2] == 1) {
+        return Err(DimensionMismatchError::new_err(format!(
+            "DLPack tensor must have shape (height, width) or (height, width, 1), got {:?}", shape
+        )));
+    }
+    let (height, width) = (shape[0], shape[1]);
+    if height <= 0 || width <= 0 || height > u32::MAX as i64 || width > u32::MAX as i64 {
+        return Err(DimensionMismatchError::new_err(format!("DLPack tensor has unusable shape {:?}", shape)));
+    }
+    let (row_stride, column_stride) = if tensor.strides.is_null() {
+        (width * shape.get(2).copied().unwrap_or(1), shape.get(2).copied().unwrap_or(1))
+    } else {
+        let strides = std::slice::from_raw_parts(tensor.strides, ndim);
+        (strides[0], strides[1])
+    };
+
+    let (width, height) = (width as usize, height as usize);
+    let base = (tensor.data as *const u8).add(tensor.byte_offset as usize);
+    let mut pixels = Vec::with_capacity(width * height);
+    for row in 0..height {
+        let row_start = base.offset(row as isize * row_stride as isize);
+        if column_stride == 1 {
+            pixels.extend_from_slice(std::slice::from_raw_parts(row_start, width));
+        } else {
+            pixels.extend((0..width).map(|column| *row_start.offset(column as isize * column_stride as isize)));
+        }
+    }
+    Ok((pixels, width as u32, height as u32))
+}
@@ -0,0 +1,203 @@
+// THEORY:
+// The event sink pushes trigger events to a local consumer over a Unix domain socket, one
+// JSON object per line, so another service can react to triggers without polling the
+// detector. Events are written from inside the frame call that fires them, so the socket
+// must never hold a frame up for long: writes block for at most `WRITE_TIMEOUT`, and a
+// consumer that stops reading long enough to fill the socket buffer is treated as gone.
+//
+// A lost connection is retried only when there is something to send, first after
+// `RECONNECT_INTERVAL`, then twice as long after every failed attempt, up to
+// `MAX_RECONNECT_INTERVAL`, so a consumer that stays away costs a frame call one failed
+// connect now and then rather than every second. Events that could not be written are
+// held or discarded according to `EventSinkPolicy`. A line cut off by a failed write is
+// never completed on the old connection; it is sent again in full after reconnecting.
+
+use std::collections::VecDeque;
+use std::io;
+#[cfg(unix)]
+use std::io::Write;
+#[cfg(unix)]
+use std::os::unix::net::UnixStream;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+#[cfg(feature = "python")]
+use pyo3::prelude::*;
+use serde::Serialize;
+
+use crate::config::EventSinkPolicy;
+
+/// `version` of every event sink message; bumped when a field changes meaning or goes away
+pub const EVENT_SCHEMA_VERSION: u32 = 1;
+
+const WRITE_TIMEOUT: Duration = Duration::from_millis(50);
+const RECONNECT_INTERVAL: Duration = Duration::from_secs(1);
+const MAX_RECONNECT_INTERVAL: Duration = Duration::from_secs(30);
+
+/// One JSON line written to the event sink (see README "Event Sink")
+#[derive(Serialize)]
+pub(crate) struct TriggerMessage<'a> {
+    pub version: u32,
+    pub event: &'static str,           // Always "trigger" for now
+    pub detector: Option<&'a str>,     // `name` from the config
+    pub frame_count: u64,
+    pub timestamp_ms: u64,
+    pub confidence: f32,
+    pub scene_state: &'a str,
+    pub trigger_reason: Option<&'a str>,
+    pub disturbed_fraction: f32,
+    pub blob_count: usize,             // Every tracked blob, before `max_reported_blobs`
+}
+
+/// Delivery counters of the event sink, from `event_sink_stats`
+#[cfg_attr(feature = "python", pyclass(get_all))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct EventSinkStats {
+    pub path: String,
+    pub connected: bool,  // Whether the socket is currently connected
+    pub sent: u64,        // Events written in full
+    pub buffered: usize,  // Events waiting for a reconnect (`EventSinkPolicy::Buffer`)
+    pub dropped: u64,     // Events discarded: while disconnected under `Drop`, or evicted from a full buffer
+    pub reconnects: u64,  // Successful reconnects after a lost connection
+}
+
+/// Unix socket writer with its pending lines and counters
+pub(crate) struct EventSink {
+    path: PathBuf,
+    stream: Option<UnixStream>,
+    pending: VecDeque<String>, // Serialized lines not written yet, oldest first, each ending in '\n'
+    last_attempt: Instant,     // Latest time the connection failed or a reconnect was tried
+    retry_delay: Duration,     // Wait after `last_attempt` before the next reconnect
+    sent: u64,
+    dropped: u64,
+    reconnects: u64,
+}
+
+impl EventSink {
+    /// Connect to the socket at `path`; the first connection must succeed
+    pub(crate) fn connect(path: &Path) -> io::Result<Self> {
+        Ok(Self {
+            path: path.to_path_buf(),
+            stream: Some(Self::open(path)?),
+            pending: VecDeque::new(),
+            last_attempt: Instant::now(),
+            retry_delay: RECONNECT_INTERVAL,
+            sent: 0,
+            dropped: 0,
+            reconnects: 0,
+        })
+    }
+
+    fn open(path: &Path) -> io::Result<UnixStream> {
+        let stream = UnixStream::connect(path)?;
+        stream.set_write_timeout(Some(WRITE_TIMEOUT))?;
+        Ok(stream)
+    }
+
+    /// Queue `message` and try to deliver everything pending. Under `Drop`, whatever could
+    /// not be written right away is discarded; under `Buffer`, the oldest events are evicted
+    /// to keep at most `capacity` waiting
+    pub(crate) fn send(&mut self, message: &TriggerMessage<'_>, policy: EventSinkPolicy, capacity: usize) {
+        match serde_json::to_string(message) {
+            Ok(line) => self.pending.push_back(line + "\n"),
+            Err(e) => log::warn!("Event sink message could not be serialized: {}", e),
+        }
+        self.flush();
+        let keep = if policy == EventSinkPolicy::Buffer { capacity } else { 0 };
+        while self.pending.len() > keep {
+            self.pending.pop_front();
+            self.dropped += 1;
+        }
+    }
+
+    /// Reconnect if needed (with backoff) and write the pending lines, oldest first
+    pub(crate) fn flush(&mut self) {
+        self.deliver(false);
+    }
+
+    /// Last delivery attempt before the sink goes away: reconnects even inside the backoff
+    pub(crate) fn flush_final(&mut self) {
+        self.deliver(true);
+    }
+
+    fn deliver(&mut self, ignore_backoff: bool) {
+        if self.pending.is_empty() {
+            return;
+        }
+        if self.stream.is_none() {
+            if !ignore_backoff && self.last_attempt.elapsed() < self.retry_delay {
+                return;
+            }
+            self.last_attempt = Instant::now();
+            match Self::open(&self.path) {
+                Ok(stream) => {
+                    log::info!("Event sink reconnected to '{}'", self.path.display());
+                    self.stream = Some(stream);
+                    self.reconnects += 1;
+                    self.retry_delay = RECONNECT_INTERVAL;
+                }
+                Err(e) => {
+                    self.retry_delay = (self.retry_delay * 2).min(MAX_RECONNECT_INTERVAL);
+                    log::debug!(
+                        "Event sink reconnect to '{}' failed: {}; next attempt in {:?}",
+                        self.path.display(), e, self.retry_delay
+                    );
+                    return;
+                }
+            }
+        }
+        while let (Some(stream), Some(line)) = (self.stream.as_mut(), self.pending.front()) {
+            if let Err(e) = stream.write_all(line.as_bytes()) {
+                log::warn!("Event sink lost its connection to '{}': {}", self.path.display(), e);
+                self.stream = None;
+                self.last_attempt = Instant::now();
+                return;
+            }
+            self.pending.pop_front();
+            self.sent += 1;
+        }
+    }
+
+    pub(crate) fn stats(&self) -> EventSinkStats {
+        EventSinkStats {
+            path: self.path.display().to_string(),
+            connected: self.stream.is_some(),
+            sent: self.sent,
+            buffered: self.pending.len(),
+            dropped: self.dropped,
+            reconnects: self.reconnects,
+        }
+    }
+}
+
+impl Drop for EventSink {
+    /// Events still pending are lost with the sink; count and report them
+    fn drop(&mut self) {
+        if !self.pending.is_empty() {
+            self.dropped += self.pending.len() as u64;
+            log::warn!(
+                "Event sink to '{}' closed with {} undelivered events ({} dropped in total)",
+                self.path.display(), self.pending.len(), self.dropped
+            );
+        }
+    }
+}
+
+/// Stand-in where the standard library has no Unix domain sockets; every connect fails
+#[cfg(not(unix))]
+struct UnixStream;
+
+#[cfg(not(unix))]
+impl UnixStream {
+    fn connect(_path: &Path) -> io::Result<Self> {
+        Err(io::Error::new(io::ErrorKind::Unsupported, "Unix domain sockets are not available on this platform"))
+    }
+
+    fn set_write_timeout(&self, _timeout: Option<Duration>) -> io::Result<()> {
+        Ok(())
+    }
+
+    fn write_all(&mut self, _buf: &[u8]) -> io::Result<()> {
+        Err(io::ErrorKind::Unsupported.into())
+    }
+}
@@ -14,7 +14,7 @@
 // - Scene state-based triggering (DISTURBED events only for reduced sensitivity)
 // - Intelligent cooldowns (prevent API spam during rapid state changes)
 // - Error resilience (graceful handling of dimension mismatches)
-// - PyO3 integration (seamless Python interoperability)
+// - Plain Rust core (`CoreDetector`) with a thin PyO3 wrapper (`FrameChangeDetector`)
 //
 // This module transforms raw video streams into actionable intelligence,
 // enabling the AI companion to respond meaningfully to environmental changes
@@ -24,272 +24,3868 @@
 // - Lazy pipeline initialization may cause first frame processing delays
 // - Scene state transitions depend on Waldo Vision's internal thresholds (not fully tunable)
 // - Buffer overflow protection may skip frames with unexpected dimensions
-// - Cooldown logic is hardcoded (1s volatile, 0.25s disturbed) - needs API configuration
+// - Base cooldown lengths are fixed (1s volatile, 0.25s disturbed). `adaptive_cooldown` can
+//   shorten the disturbed one, `global_min_trigger_interval_secs` adds a floor across paths
+//   and `clear_*_cooldown` ends one early, but neither length is configurable itself
 // - Error handling in PyO3 conversion may mask underlying Waldo Vision issues
 // - No persistence between restarts (scene learning resets on each startup)
 // - Dimension mismatch handling is defensive but doesn't fix root cause
+//   (frames that differ from the initialized size raise DimensionMismatchError unless
+//   `frame_size_policy` allows a rebuild)
 
+use std::hash::{Hash, Hasher};
+use std::time::Instant;
+
+#[cfg(feature = "python")]
 use pyo3::prelude::*;
-use waldo_vision::pipeline::{VisionPipeline, PipelineConfig, Report};
+use serde::{Deserialize, Serialize};
+use waldo_vision::pipeline::{VisionPipeline, PipelineConfig, Report, MentionData, SceneState, ChunkStatus, Moment, TrackedBlob, TrackedState};
+use waldo_vision::pipeline::FrameAnalysis as PipelineAnalysis;
+use waldo_vision::core_modules::smart_blob::{Point, SmartBlob};
+use zune_core::colorspace::ColorSpace as ZuneColorSpace;
+use image::metadata::Orientation;
+
+mod config;
+mod event_sink;
+pub use event_sink::{EventSinkStats, EVENT_SCHEMA_VERSION};
+use event_sink::{EventSink, TriggerMessage};
+pub use config::{BackgroundModel, BlobRanking, ChannelCombine, ConfidenceCurve, DecoderBackend, DetectorConfig, DuplicateTimestampPolicy, Equalization, EventSinkPolicy, FirstFramePolicy, FrameSizePolicy, OutputCoords, PreprocessStep, ResizeFilter, Rotation, SensitivityProfile, SensitivitySchedule, TruncatedJpegPolicy, UniformFramePolicy};
+
+#[cfg(feature = "python")]
+mod python;
+#[cfg(feature = "dlpack")]
+mod dlpack;
+#[cfg(feature = "arrow")]
+mod arrow;
+#[cfg(feature = "python")]
+pub use python::{FrameChangeDetector, FrameStream};
+
+/// Error returned by `CoreDetector`; the Python bindings raise each variant as the exception
+/// of the same name (see README "Errors")
+#[derive(Debug)]
+pub enum DetectorError {
+    Decode(String),             // DecodeError: the frame could not be decoded into an image
+    Config(String),             // ConfigError: an option or argument is invalid
+    DimensionMismatch(String),  // DimensionMismatchError: the frame does not match the locked size
+    Calibration(String),        // CalibrationError: still calibrating past `error_if_calibrating_after`
+    Closed(String),             // DetectorClosedError: the detector was closed
+    DuplicateTimestamp(String), // DuplicateTimestampError: same timestamp as the previous frame
+    PipelinePanic(String),      // PipelinePanicError: the Waldo Vision pipeline panicked
+    Serialize(String),          // A result or config could not be serialized
+    Io { path: std::path::PathBuf, source: std::io::Error }, // A frame file could not be read
+    EventSink { path: std::path::PathBuf, source: std::io::Error }, // The event sink socket could not be connected
+}
+
+impl std::fmt::Display for DetectorError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Self::Decode(message)
+            | Self::Config(message)
+            | Self::DimensionMismatch(message)
+            | Self::Calibration(message)
+            | Self::Closed(message)
+            | Self::DuplicateTimestamp(message)
+            | Self::PipelinePanic(message)
+            | Self::Serialize(message) => f.write_str(message),
+            Self::Io { path, source } => write!(f, "Cannot read frame file '{}': {}", path.display(), source),
+            Self::EventSink { path, source } => write!(f, "Cannot connect event sink to '{}': {}", path.display(), source),
+        }
+    }
+}
+
+impl std::error::Error for DetectorError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Io { source, .. } | Self::EventSink { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+/// Decoded frame planes, one pipeline input per plane
+#[derive(Clone)]
+struct DecodedFrame {
+    luma: Vec<u8>,
+    chroma: Option<[Vec<u8>; 2]>, // Blue- and red-difference planes (multi-channel mode only)
+    width: u32,
+    height: u32,
+}
+
+/// Luma and chroma pipeline analyses of one frame, or the payload of a pipeline panic
+type PipelineOutcome = std::thread::Result<(PipelineAnalysis, Option<Vec<PipelineAnalysis>>)>;
+
+/// Untouched source luma kept for a trigger thumbnail, with its maximum dimension
+type ThumbnailSource = (Vec<u8>, u32);
+
+impl DecodedFrame {
+    /// Apply an EXIF-style orientation to every plane, swapping dimensions for quarter turns
+    fn oriented(self, orientation: Orientation) -> Self {
+        if orientation == Orientation::NoTransforms {
+            return self;
+        }
+        let (width, height) = (self.width, self.height);
+        let orient = |plane: Vec<u8>| {
+            let plane = image::GrayImage::from_raw(width, height, plane).expect("plane matches frame size");
+            let mut image = image::DynamicImage::ImageLuma8(plane);
+            image.apply_orientation(orientation);
+            image.into_luma8()
+        };
+        let luma = orient(self.luma);
+        let chroma = self.chroma.map(|[cb, cr]| [orient(cb).into_raw(), orient(cr).into_raw()]);
+        Self { width: luma.width(), height: luma.height(), luma: luma.into_raw(), chroma }
+    }
+}
+
+/// How the analyzed buffer maps back onto the decoded source frame
+#[derive(Debug, Clone, Copy)]
+struct FrameGeometry {
+    source_size: (u32, u32),    // Decoded frame dimensions
+    offset: (f64, f64),         // Source-pixel position of the analysis buffer's origin
+    scale: (f64, f64),          // Source pixels per analysis pixel
+}
+
+impl FrameGeometry {
+    /// Identity mapping: the analysis buffer is the whole source frame
+    fn identity(width: u32, height: u32) -> Self {
+        Self { source_size: (width, height), offset: (0.0, 0.0), scale: (1.0, 1.0) }
+    }
+
+    /// Map a point in source-frame pixels into analysis pixels
+    fn source_to_analysis(&self, x: f64, y: f64) -> (f64, f64) {
+        ((x - self.offset.0) / self.scale.0, (y - self.offset.1) / self.scale.1)
+    }
+
+    /// Map a point in analysis pixels into the requested output space
+    fn map_point(&self, coords: OutputCoords, x: f64, y: f64) -> (f64, f64) {
+        let source = (self.offset.0 + x * self.scale.0, self.offset.1 + y * self.scale.1);
+        match coords {
+            OutputCoords::PixelsAnalysis => (x, y),
+            OutputCoords::PixelsOriginal => source,
+            OutputCoords::Normalized0to1 => (
+                source.0 / self.source_size.0.max(1) as f64,
+                source.1 / self.source_size.1.max(1) as f64,
+            ),
+        }
+    }
+}
+
+/// One chunk-aligned tile of the analysis frame with its own pipeline (`tile_size`)
+struct Tile {
+    rect: (u32, u32, u32, u32), // (x, y, width, height) in analysis pixels
+    grid: (u32, u32, u32),      // (column, row) of the tile origin and its width, in chunks
+    pipeline: VisionPipeline,
+}
+
+/// The luma pipeline: one over the whole analysis frame, or one per tile with the results
+/// stitched back onto the frame's chunk grid
+enum LumaPipeline {
+    Whole(Box<VisionPipeline>),
+    Tiled { tiles: Vec<Tile>, width: u32, grid: (u32, u32) },
+}
+
+impl LumaPipeline {
+    /// Build the pipeline(s) for a config sized to the analysis frame. Tiles are cut from the
+    /// top-left; a ragged edge narrower than one chunk is dropped, as the whole-frame grid
+    /// drops it too, so the tile grids always add up to the frame grid.
+    fn new(config: &PipelineConfig, tile_size: Option<(u32, u32)>) -> Self {
+        let (width, height) = (config.image_width, config.image_height);
+        let (chunk_w, chunk_h) = (config.chunk_width, config.chunk_height);
+        let mut tiles = Vec::new();
+        if let Some((tile_w, tile_h)) = tile_size.filter(|&(w, h)| w < width || h < height) {
+            for y in (0..height).step_by(tile_h as usize) {
+                for x in (0..width).step_by(tile_w as usize) {
+                    let rect = (x, y, tile_w.min(width - x), tile_h.min(height - y));
+                    if rect.2 < chunk_w || rect.3 < chunk_h {
+                        continue;
+                    }
+                    let mut tile_config = config.clone();
+                    tile_config.image_width = rect.2;
+                    tile_config.image_height = rect.3;
+                    let grid = (x / chunk_w, y / chunk_h, rect.2 / chunk_w);
+                    tiles.push(Tile { rect, grid, pipeline: VisionPipeline::new(tile_config) });
+                }
+            }
+        }
+        if tiles.is_empty() {
+            return Self::Whole(Box::new(VisionPipeline::new(config.clone())));
+        }
+        Self::Tiled { tiles, width, grid: (width / chunk_w, height / chunk_h) }
+    }
+
+    /// Analyze one row-major luma plane of the analysis frame
+    fn process_frame(&mut self, luma: &[u8]) -> PipelineAnalysis {
+        match self {
+            Self::Whole(pipeline) => pipeline.process_frame(&CoreDetector::to_pipeline_layout(luma)),
+            Self::Tiled { tiles, width, grid } => {
+                // Only one tile's RGBA copy is alive at a time
+                let analyses: Vec<PipelineAnalysis> = tiles.iter_mut().map(|tile| {
+                    let plane = CoreDetector::crop_plane(luma, *width, tile.rect);
+                    tile.pipeline.process_frame(&CoreDetector::to_pipeline_layout(&plane))
+                }).collect();
+                Self::stitch(tiles, analyses, *grid)
+            }
+        }
+    }
+
+    /// Merge per-tile analyses into one on the frame grid: chunk states go to their global
+    /// cell, blobs and moments move into frame grid coordinates with ids made unique per
+    /// tile (`id * tiles + index`), and the scene takes the most active tile's state
+    fn stitch(tiles: &[Tile], analyses: Vec<PipelineAnalysis>, (grid_w, grid_h): (u32, u32)) -> PipelineAnalysis {
+        let tile_count = tiles.len() as u64;
+        let mut status_map = vec![ChunkStatus::Stable; (grid_w * grid_h) as usize];
+        let mut tracked_blobs = Vec::new();
+        let mut scene_state = SceneState::Calibrating;
+        let mut significant_event_count = 0;
+        let mut mention: Option<MentionData> = None;
+        for (index, (tile, analysis)) in tiles.iter().zip(analyses).enumerate() {
+            let (column, row, columns) = tile.grid;
+            let unique_id = |id: u64| id * tile_count + index as u64;
+            let shift = |point: &mut Point| {
+                point.x += column;
+                point.y += row;
+            };
+            let shift_position = |position: &mut (f64, f64)| {
+                position.0 += column as f64;
+                position.1 += row as f64;
+            };
+            let shift_blob = |blob: &mut SmartBlob| {
+                blob.id = unique_id(blob.id);
+                shift(&mut blob.bounding_box.0);
+                shift(&mut blob.bounding_box.1);
+                blob.chunk_coords.iter_mut().for_each(shift);
+                shift_position(&mut blob.center_of_mass);
+            };
+            for (cell, status) in analysis.status_map.into_iter().enumerate() {
+                let (x, y) = (column + cell as u32 % columns, row + cell as u32 / columns);
+                status_map[(y * grid_w + x) as usize] = status;
+            }
+            for mut tracked in analysis.tracked_blobs {
+                tracked.id = unique_id(tracked.id);
+                tracked.parent_id = tracked.parent_id.map(unique_id);
+                shift_blob(&mut tracked.latest_blob);
+                tracked.position_history.iter_mut().for_each(shift_position);
+                tracked_blobs.push(tracked);
+            }
+            if let Report::SignificantMention(data) = analysis.report {
+                let shift_moment = |mut moment: Moment| {
+                    moment.id = unique_id(moment.id);
+                    moment.path.iter_mut().for_each(shift_position);
+                    moment.blob_history.iter_mut().for_each(shift_blob);
+                    moment
+                };
+                let merged = mention.get_or_insert_with(|| MentionData {
+                    new_significant_moments: Vec::new(),
+                    completed_significant_moments: Vec::new(),
+                    is_global_disturbance: false,
+                });
+                merged.new_significant_moments.extend(data.new_significant_moments.into_iter().map(shift_moment));
+                merged.completed_significant_moments.extend(data.completed_significant_moments.into_iter().map(shift_moment));
+                merged.is_global_disturbance |= data.is_global_disturbance;
+            }
+            if CoreDetector::scene_state_rank(&analysis.scene_state) > CoreDetector::scene_state_rank(&scene_state) {
+                scene_state = analysis.scene_state;
+            }
+            significant_event_count += analysis.significant_event_count;
+        }
+        PipelineAnalysis {
+            report: mention.map_or(Report::NoSignificantMention, Report::SignificantMention),
+            status_map,
+            tracked_blobs,
+            scene_state,
+            significant_event_count,
+        }
+    }
+}
+
+/// Base cooldown between whole-frame Disturbed triggers
+const DISTURBED_COOLDOWN_SECS: f64 = 0.25;
+
+/// Cooldown between Volatile escalation triggers
+const VOLATILE_COOLDOWN_SECS: f64 = 1.0;
+
+/// Cooldown between triggers of the same region (matches the whole-frame Disturbed cooldown)
+const REGION_COOLDOWN_SECS: f64 = DISTURBED_COOLDOWN_SECS;
+
+/// Confidence reported for triggers forced by sustained Volatile escalation
+const VOLATILE_ESCALATION_CONFIDENCE: f32 = 60.0;
+
+/// Fixed confidence of the one-shot `alert_on_first_motion` trigger
+const FIRST_MOTION_CONFIDENCE: f32 = 100.0;
+
+/// Frames a fresh pipeline needs before it leaves Calibrating: its 30-frame calibration,
+/// counted from the frame after the first
+const CALIBRATION_FRAMES: u32 = 31;
+
+/// Seed frames fed to a fresh pipeline: enough to fill its 20-frame chunk history and
+/// finish its 30-frame calibration
+const REFERENCE_SEED_FRAMES: u32 = CALIBRATION_FRAMES;
+
+/// Frames of per-chunk average history Waldo Vision keeps (its background, in effect)
+const PIPELINE_HISTORY_FRAMES: usize = 20;
+
+/// Mean-luma change below which Waldo Vision calls a chunk Stable (hard-coded in 0.2)
+const PIPELINE_STABLE_DELTA: f64 = 2.0;
 
-/// Waldo Vision-powered frame change detector with intelligent cooldowns
-#[pyclass]
-pub struct FrameChangeDetector {
-    pipeline: Option<VisionPipeline>,  // Initialize lazily with first frame dimensions
+/// Side of the square pipeline chunks in pixels; config validation checks grid alignment against it
+const CHUNK_SIZE: u32 = 10;
+
+/// Leading byte of `FrameAnalysis.to_bytes()`. The postcard body is not self-describing, so
+/// bump this whenever a field of `FrameAnalysis` or `TrackedBlob` is added, removed or reordered
+const RESULT_WIRE_VERSION: u8 = 3;
+
+/// Relative aspect-ratio difference up to which a new frame size counts as a pure rescale
+/// (absorbs rounding, e.g. 854x480 against 1920x1080)
+const ASPECT_RATIO_TOLERANCE: f64 = 0.01;
+
+/// Bounding-box aspect ratio (either way) beyond which a blob is tall or wide, not square
+const BLOB_SHAPE_ASPECT_RATIO: f64 = 1.5;
+
+/// Seconds left before a cooldown timer set at `last_trigger` lets the next trigger through;
+/// a timer of None has never fired (or was cleared) and never blocks
+fn cooldown_remaining(last_trigger: Option<f64>, cooldown_secs: f64, now: f64) -> f64 {
+    last_trigger.map_or(0.0, |last| (last + cooldown_secs - now).max(0.0))
+}
+
+/// Inter-frame deltas the fps estimator takes its median over
+const FPS_WINDOW_DELTAS: usize = 30;
+
+/// Deltas needed before `estimated_fps()` reports anything
+const FPS_MIN_DELTAS: usize = 5;
+
+/// Histogram bins a CLAHE tile may hold, as a multiple of the uniform bin height
+const CLAHE_CLIP_LIMIT: f32 = 2.0;
+
+/// A named rectangle (source-frame pixels) that triggers independently of the whole frame
+#[derive(Debug, Clone)]
+struct Region {
+    name: String,
+    rect: (u32, u32, u32, u32),   // (x, y, width, height) in decoded-frame pixels
+    last_trigger: Option<f64>,    // Per-region cooldown timer (None = never fired)
+}
+
+/// Wall time per processing stage of the frame in flight (profile mode)
+#[derive(Debug, Clone)]
+struct StageProfile {
+    started: Instant,
+    stages_ms: std::collections::BTreeMap<String, f64>,
+}
+
+/// Observability for caller-supplied frame timestamps; never affects triggering
+#[derive(Debug, Clone, Default)]
+struct TimingDiagnostics {
+    last_timestamp_ms: Option<u64>,
+    out_of_order_frames: u64,      // Timestamp went backwards relative to the previous frame
+    duplicate_timestamps: u64,     // Timestamp identical to the previous frame
+    skipped_duplicates: u64,       // Duplicates answered with the previous result (`DuplicateTimestampPolicy::Skip`)
+    min_delta_ms: Option<u64>,     // Smallest forward inter-frame delta seen
+    max_delta_ms: Option<u64>,     // Largest forward inter-frame delta seen
+    recent_deltas_ms: std::collections::VecDeque<u64>, // Latest forward deltas, for the fps estimate
+    delta_window: usize,           // Capacity of `recent_deltas_ms` (0 = estimator off)
+}
+
+impl TimingDiagnostics {
+    /// Empty stats; `low_memory` turns the fps estimator's delta window off
+    fn new(low_memory: bool) -> Self {
+        Self { delta_window: if low_memory { 0 } else { FPS_WINDOW_DELTAS }, ..Self::default() }
+    }
+
+    fn observe(&mut self, timestamp_ms: u64) {
+        if let Some(last) = self.last_timestamp_ms {
+            match timestamp_ms.cmp(&last) {
+                std::cmp::Ordering::Less => self.out_of_order_frames += 1,
+                std::cmp::Ordering::Equal => self.duplicate_timestamps += 1,
+                std::cmp::Ordering::Greater => {
+                    let delta = timestamp_ms - last;
+                    self.min_delta_ms = Some(self.min_delta_ms.map_or(delta, |d| d.min(delta)));
+                    self.max_delta_ms = Some(self.max_delta_ms.map_or(delta, |d| d.max(delta)));
+                    if self.delta_window > 0 {
+                        if self.recent_deltas_ms.len() == self.delta_window {
+                            self.recent_deltas_ms.pop_front();
+                        }
+                        self.recent_deltas_ms.push_back(delta);
+                    }
+                }
+            }
+        }
+        self.last_timestamp_ms = Some(timestamp_ms);
+    }
+
+    /// Frames per second from the median recent delta; None until enough deltas arrive
+    fn estimated_fps(&self) -> Option<f32> {
+        if self.recent_deltas_ms.len() < FPS_MIN_DELTAS {
+            return None;
+        }
+        let mut deltas: Vec<u64> = self.recent_deltas_ms.iter().copied().collect();
+        deltas.sort_unstable();
+        let middle = deltas.len() / 2;
+        let median_ms = if deltas.len().is_multiple_of(2) {
+            (deltas[middle - 1] + deltas[middle]) as f32 / 2.0
+        } else {
+            deltas[middle] as f32
+        };
+        Some(1000.0 / median_ms)
+    }
+}
+
+/// A blob tracked by Waldo Vision, with coordinates in the detector's `output_coords` space
+#[cfg_attr(feature = "python", pyclass(name = "TrackedBlob", get_all))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlobInfo {
+    pub id: u64,                          // Tracker id, persistent across frames
+    pub state: String,                    // NEW / TRACKING / LOST / ANOMALOUS (REMOVED in `removed_objects`)
+    pub bbox: (f64, f64, f64, f64),       // (x_min, y_min, x_max, y_max)
+    pub center: (f64, f64),               // Luminance-weighted center of mass
+    pub size_in_chunks: usize,            // Blob area in grid chunks
+    pub age: u32,                         // Frames since the tracker first saw it
+    pub size_hint: String,                // small / medium / large, from bounding-box share of the frame
+    pub shape_hint: String,               // tall / wide / square, from bounding-box aspect ratio
+}
+
+/// One of the most-changed chunks of the latest frame, from `CoreDetector::hotspots`
+#[cfg_attr(feature = "python", pyclass(get_all))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Hotspot {
+    pub chunk: (u32, u32),                // (column, row) on the analysis chunk grid
+    pub center: (f64, f64),               // Chunk center in `output_coords` space
+    pub bbox: (f64, f64, f64, f64),       // Chunk area (x_min, y_min, x_max, y_max) in `output_coords` space
+    pub disturbance: f64,                 // Absolute mean-luma change of the chunk, in 0-255 levels
+}
+
+/// A fired trigger taken from the trigger queue by `drain_triggers`; with debouncing, one
+/// event stands for every trigger coalesced into it
+#[cfg_attr(feature = "python", pyclass(get_all))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct TriggerEvent {
+    pub frame_count: u64,                 // `frame_count` of the representative (highest-confidence) trigger
+    pub timestamp_ms: u64,                // Timestamp of the representative trigger
+    pub confidence: f32,                  // Confidence of the representative trigger
+    pub trigger_reason: Option<String>,   // Decision path of the representative trigger
+    pub first_timestamp_ms: u64,          // Earliest timestamp coalesced into this event
+    pub last_timestamp_ms: u64,           // Latest timestamp coalesced into this event
+    pub coalesced_count: u32,             // Triggers in this event (1 without debouncing)
+}
+
+/// Per-frame result of the detector, returned by `analyze_frame`
+#[cfg_attr(feature = "python", pyclass(get_all))]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FrameAnalysis {
+    pub should_trigger: bool,      // Whether expensive AI analysis should run for this frame
+    pub confidence: f32,           // Trigger confidence (0-100), 0 when not triggering
+    pub smoothed_confidence: f32,  // EMA of `confidence` (equal to it when smoothing is off)
+    pub confidence_normalized: f32, // `confidence` / 100 (0-1), after `confidence_curve` like it
+    pub tracked_objects: usize,    // Number of blobs tracked by Waldo Vision
+    pub scene_state: String,       // CALIBRATING / STABLE / VOLATILE / DISTURBED
+    pub previous_scene_state: Option<String>, // `scene_state` of the previous analyzed frame; None on the first
+    pub frame_count: u64,          // Frames processed so far, including this one
+    pub timestamp_ms: u64,         // Caller-supplied frame timestamp, echoed back
+    pub frame_id: Option<String>,  // Caller-supplied opaque tag, echoed back verbatim
+    pub disturbed_fraction: f32,   // Fraction of chunks currently in motion (0-1)
+    pub frozen: bool,              // Feed appears frozen (see `freeze_frames`)
+    pub calmed: bool,              // Edge mode or `trigger_on_calm`: scene returned to Stable on this frame
+    pub blobs: Vec<BlobInfo>,      // Tracked blobs in `output_coords` space (at most `max_reported_blobs`)
+    pub total_blobs: usize,        // Blobs tracked before truncation
+    pub coverage_fraction: f32,    // Share of the analyzed grid covered by active (non-LOST) blobs, overlaps counted once
+    pub removed_objects: Vec<BlobInfo>, // Established blobs whose track ended on this frame, last-known position
+    pub region_triggers: Vec<String>, // Names of regions that triggered on this frame
+    pub region_fractions: std::collections::BTreeMap<String, f32>, // Per-region disturbed fraction
+    pub channel_states: Vec<String>, // Per-channel states [luma, cb, cr] in multi-channel mode, else empty
+    pub scene_state_scores: std::collections::BTreeMap<String, f32>, // Soft 0-1 score per scene state name
+    pub thumbnail_png_b64: Option<String>, // Base64 grayscale PNG of the frame, only on triggering frames
+    pub trigger_reason: Option<String>, // Decision path behind `should_trigger`, only on triggering frames
+    pub stage_timings_ms: Option<std::collections::BTreeMap<String, f64>>, // Per-stage wall time, only with `profile`
+    pub uniform_frame: bool,       // Frame was near-uniform (luma variance below `uniform_variance_threshold`)
+    pub calibration_progress: f32, // Share of calibration done (0-1), 1.0 once the pipeline has left Calibrating
+    pub timed_out: bool,           // The pipeline missed `watchdog_timeout_ms` on this frame; the result is a placeholder
+}
+
+impl FrameAnalysis {
+    /// Wire format version written by `to_bytes` and required by `from_bytes`
+    pub const WIRE_FORMAT_VERSION: u8 = RESULT_WIRE_VERSION;
+
+    /// Compact binary encoding for IPC: one version byte, then the postcard-encoded fields
+    /// (see README "Binary Results")
+    pub fn to_bytes(&self) -> Result<Vec<u8>, DetectorError> {
+        let mut bytes = vec![RESULT_WIRE_VERSION];
+        bytes.extend(postcard::to_allocvec(self)
+            .map_err(|e| DetectorError::Serialize(format!("Could not serialize FrameAnalysis: {}", e)))?);
+        Ok(bytes)
+    }
+
+    /// Rebuild a result from `to_bytes()` output; `DetectorError::Decode` on a version
+    /// mismatch or malformed input
+    pub fn from_bytes(data: &[u8]) -> Result<Self, DetectorError> {
+        let (&version, body) = data.split_first()
+            .ok_or_else(|| DetectorError::Decode("FrameAnalysis bytes are empty".into()))?;
+        if version != RESULT_WIRE_VERSION {
+            return Err(DetectorError::Decode(format!(
+                "FrameAnalysis bytes use wire format version {}, this build reads version {}",
+                version, RESULT_WIRE_VERSION
+            )));
+        }
+        let (analysis, rest) = postcard::take_from_bytes::<Self>(body)
+            .map_err(|e| DetectorError::Decode(format!("Invalid FrameAnalysis bytes: {}", e)))?;
+        if !rest.is_empty() {
+            return Err(DetectorError::Decode(format!("Invalid FrameAnalysis bytes: {} trailing bytes", rest.len())));
+        }
+        Ok(analysis)
+    }
+}
+
+/// Identity and progress of a detector, from `CoreDetector::status`
+#[derive(Debug, Clone, PartialEq)]
+pub struct DetectorStatus {
+    pub name: Option<String>,            // Detector label from the config
+    pub frame_count: u64,                // Frames processed so far
+    pub scene_state: Option<&'static str>, // Scene state of the latest frame; None before the first
+    pub frame_size: Option<(u32, u32)>,  // Decoded frame size locked in by the first frame
+    pub roi: Option<(u32, u32, u32, u32)>, // Effective (clipped) ROI
+    pub disturbed_triggers: u64,         // Triggers from the Disturbed path
+    pub volatile_triggers: u64,          // Triggers from sustained Volatile escalation
+    pub edge_triggers: u64,              // Triggers from edge mode activity onsets
+    pub suppressed_triggers: u64,        // Trigger-eligible frames held back by a cooldown
+    pub truncated_frames: u64,           // JPEGs missing their end-of-image marker
+}
+
+/// Timestamp diagnostics, from `CoreDetector::timing_diagnostics`
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TimingReport {
+    pub out_of_order_frames: u64,        // Timestamp went backwards relative to the previous frame
+    pub duplicate_timestamps: u64,       // Timestamp identical to the previous frame
+    pub skipped_duplicates: u64,         // Duplicates answered with the previous result
+    pub min_delta_ms: Option<u64>,       // Smallest forward inter-frame delta seen
+    pub max_delta_ms: Option<u64>,       // Largest forward inter-frame delta seen
+    pub last_timestamp_ms: Option<u64>,  // Latest frame timestamp
+    pub estimated_fps: Option<f32>,      // See `CoreDetector::estimated_fps`
+}
+
+/// Waldo Vision-powered frame change detector with intelligent cooldowns, usable from Rust
+/// directly; `FrameChangeDetector` wraps it for Python
+pub struct CoreDetector {
+    pipeline: Option<LumaPipeline>,    // Initialize lazily with first frame dimensions
+    chroma_pipelines: Vec<VisionPipeline>, // Cb/Cr sub-pipelines (multi-channel mode only)
+    config: DetectorConfig,            // Options in effect: `base_config` with the active schedule profile applied
+    base_config: DetectorConfig,       // Options the detector was created with
+    active_profile: Option<String>,    // Name of the `sensitivity_schedule` profile in effect, None for the base options
     config_template: PipelineConfig,   // Template config for creating pipeline
     frame_count: u64,
-    last_volatile_trigger: f64,     // Last time we triggered on volatile state
-    last_disturbed_trigger: f64,    // Last time we triggered on disturbed state
+    last_volatile_trigger: Option<f64>,  // Last time we triggered on volatile state
+    last_disturbed_trigger: Option<f64>, // Last time we triggered on disturbed state
+    last_calm: Option<f64>,              // Last `calmed` signal under `trigger_on_calm`
+    motionless_run: u32,            // Current run of consecutive motionless frames
+    disturbance_hold_remaining: u32, // Frames left to hold Disturbed after the pipeline exits it
+    spike_promoted: bool,           // A spike promoted the current Volatile episode to Disturbed
+    volatile_run: u32,              // Current run of consecutive Volatile frames
+    unstable_run: u32,              // Current run of frames above the disturbance entry threshold
+    pipeline_frames: u64,           // Frames seen by the current pipeline instance (moment frame clock)
+    geometry: Option<FrameGeometry>, // Analysis-to-source mapping of the latest frame
+    pipeline_config: Option<PipelineConfig>, // Exact config the pipeline was created with
+    source_size: Option<(u32, u32)>, // Decoded frame dimensions locked in by the first frame
+    roi: Option<(u32, u32, u32, u32)>, // Effective ROI (x, y, width, height), clipped to the frame
+    pending_roi: Option<(i64, i64, u32, u32)>, // ROI requested before the frame size was known
+    exclusion_polygons: Vec<Vec<(u32, u32)>>, // Exclusion zones in source-frame pixels
+    exclusion_mask: Option<Vec<bool>>, // Rasterized union of the zones at the source size (rebuilt lazily)
+    stage_profile: Option<StageProfile>, // Stage timings of the frame in flight (profile mode only)
+    last_good_frame: Option<DecodedFrame>, // Latest prepared non-uniform frame (TreatAsStable stand-in)
+    previous_chunk_means: Vec<f64>, // Per-chunk mean luma of the previous analyzed frame (chunk threshold), or the Adaptive background
+    hotspots: Vec<(usize, f64)>,    // Moving chunks of the latest frame as (grid index, luma change), largest change first
+    last_report: Option<Report>,    // Waldo Vision's report for the latest analyzed frame (not kept in low-memory mode)
+    background_samples: std::collections::VecDeque<Vec<f64>>, // Per-chunk mean luma of the last K frames, oldest first (Mean/Median background)
+    exposure_reference: Option<f64>, // Mean luma of the frame the pipeline was created on (exposure compensation)
+    background_history: std::collections::VecDeque<Vec<f64>>, // Per-chunk mean luma of the latest pipeline frames, oldest first
+    temporal_frames: std::collections::VecDeque<Vec<u8>>, // Latest prepared luma planes, oldest first (temporal average)
+    temporal_sums: Vec<u32>,        // Per-pixel sum over `temporal_frames`
+    decoder_backend: DecoderBackend, // Effective JPEG decoder (TurboJpeg falls back to Image if not compiled in)
+    regions: Vec<Region>,           // Independently-triggering regions, in insertion order
+    timing: TimingDiagnostics,      // Timestamp monotonicity/jitter stats
+    previous_scene_state: Option<SceneState>, // Scene state of the previous frame
+    last_trigger_eligible: bool,    // Latest frame would have triggered if no cooldown were running
+    first_motion_alerted: bool,     // `alert_on_first_motion` fired since the pipeline last calibrated
+    confidence_ema: Option<f32>,    // Smoothed confidence so far; None until the first frame
+    previous_blobs: Vec<BlobInfo>,  // Blobs reported for the previous frame, for spotting ended tracks
+    stable_blob_ids: std::collections::BTreeMap<u64, u64>, // Deterministic mode: tracker id -> reported id
+    next_stable_blob_id: u64,
+    closed: bool,                   // Set by `close()`; frame processing is refused afterwards
+    poisoned: bool,                 // A pipeline panic with `poison_on_panic`; frames are refused until `reset()`
+    grayscale_fast_path_frames: u64, // Frames decoded from single-channel sources without color conversion
+    color_converted_frames: u64,    // Frames that needed a color-to-luma conversion
+    turbojpeg_frames: u64,          // Frames decoded by libjpeg-turbo (also counted as grayscale or color above)
+    decode_cache: std::collections::VecDeque<(u64, DecodedFrame)>, // LRU of decoded frames by input hash, most recent last
+    decode_cache_hits: u64,
+    decode_cache_misses: u64,
+    trigger_count: u64,             // Frames with `should_trigger` set
+    last_trigger_time: Option<f64>, // Cooldown-clock time of the latest `should_trigger` frame, any path
+    skipped_frames: u64,            // Uniform frames skipped under `UniformFramePolicy::Ignore`
+    decode_errors: u64,             // Frames rejected with `DecodeError`
+    truncated_frames: u64,          // JPEGs missing their end-of-image marker, rejected or padded
+    disturbed_triggers: u64,        // Triggers from the Disturbed path (spike promotion and hold included)
+    volatile_triggers: u64,         // Triggers from sustained Volatile escalation
+    edge_triggers: u64,             // Triggers from edge mode activity onsets
+    suppressed_triggers: u64,       // Trigger-eligible frames held back by a cooldown
+    recorded_analyses: std::collections::VecDeque<FrameAnalysis>, // Batch results by ascending frame_count (recording mode)
+    trigger_queue: std::collections::VecDeque<TriggerEvent>, // Fired triggers not yet drained, oldest first (`trigger_queue_size`)
+    last_analysis: Option<FrameAnalysis>, // Latest analyzed result, replayed for skipped duplicate timestamps
+    event_sink: Option<EventSink>,        // Unix socket receiving trigger events (`set_event_sink`)
 }
 
-#[pymethods]
-impl FrameChangeDetector {
-    #[new]
-    pub fn new(
-        _buffer_duration_ms: Option<u64>,
-        change_threshold: Option<f32>,
-        _frame_interval_ms: Option<u64>
-    ) -> Self {
+impl CoreDetector {
+    /// Create a detector; fails with `DetectorError::Config` if the config does not validate
+    pub fn new(config: DetectorConfig) -> Result<Self, DetectorError> {
+        Self::validate_config(&config).map_err(|errors| DetectorError::Config(errors.join("; ")))?;
+
         // Create template config - pipeline will be created lazily with actual frame dimensions
         let config_template = PipelineConfig {
             image_width: 640,          // Will be updated with actual frame width
-            image_height: 480,         // Will be updated with actual frame height  
-            chunk_width: 10,           // 10x10 analysis grid
-            chunk_height: 10,
+            image_height: 480,         // Will be updated with actual frame height
+            chunk_width: CHUNK_SIZE,   // 10x10 analysis grid
+            chunk_height: CHUNK_SIZE,
             new_age_threshold: 15,     // ~0.5s at 30fps for persistence
-            behavioral_anomaly_threshold: change_threshold.unwrap_or(5.0) as f64 / 100.0,
+            behavioral_anomaly_threshold: config.change_threshold as f64 / 100.0,
             absolute_min_blob_size: 5, // Minimum 5 chunks for valid object
             blob_size_std_dev_filter: 1.5,
             disturbance_entry_threshold: 0.3,  // 30% of chunks disturbed to trigger
             disturbance_exit_threshold: 0.1,   // 10% to exit disturbance state
             disturbance_confirmation_frames: 5, // 5 frames to confirm disturbance
         };
-        
-        Self { 
+        // Runtime fallback: without the cargo feature only the image crate is available
+        let decoder_backend = if cfg!(feature = "turbojpeg") { config.decoder_backend } else { DecoderBackend::Image };
+        let timing = TimingDiagnostics::new(config.low_memory);
+
+        Ok(Self {
             pipeline: None,            // Initialize lazily
+            chroma_pipelines: Vec::new(),
+            base_config: config.clone(),
+            active_profile: None,
+            config,
             config_template,
             frame_count: 0,
-            last_volatile_trigger: 0.0,
-            last_disturbed_trigger: 0.0,
-        }
+            last_volatile_trigger: None,
+            last_disturbed_trigger: None,
+            last_calm: None,
+            motionless_run: 0,
+            disturbance_hold_remaining: 0,
+            spike_promoted: false,
+            volatile_run: 0,
+            unstable_run: 0,
+            pipeline_frames: 0,
+            geometry: None,
+            pipeline_config: None,
+            source_size: None,
+            roi: None,
+            pending_roi: None,
+            exclusion_polygons: Vec::new(),
+            exclusion_mask: None,
+            stage_profile: None,
+            last_good_frame: None,
+            previous_chunk_means: Vec::new(),
+            background_samples: std::collections::VecDeque::new(),
+            hotspots: Vec::new(),
+            last_report: None,
+            exposure_reference: None,
+            background_history: std::collections::VecDeque::new(),
+            temporal_frames: std::collections::VecDeque::new(),
+            temporal_sums: Vec::new(),
+            decoder_backend,
+            regions: Vec::new(),
+            timing,
+            previous_scene_state: None,
+            last_trigger_eligible: false,
+            first_motion_alerted: false,
+            confidence_ema: None,
+            previous_blobs: Vec::new(),
+            stable_blob_ids: std::collections::BTreeMap::new(),
+            next_stable_blob_id: 0,
+            closed: false,
+            poisoned: false,
+            grayscale_fast_path_frames: 0,
+            color_converted_frames: 0,
+            turbojpeg_frames: 0,
+            decode_cache: std::collections::VecDeque::new(),
+            decode_cache_hits: 0,
+            decode_cache_misses: 0,
+            trigger_count: 0,
+            last_trigger_time: None,
+            skipped_frames: 0,
+            decode_errors: 0,
+            truncated_frames: 0,
+            disturbed_triggers: 0,
+            volatile_triggers: 0,
+            edge_triggers: 0,
+            suppressed_triggers: 0,
+            recorded_analyses: std::collections::VecDeque::new(),
+            trigger_queue: std::collections::VecDeque::new(),
+            last_analysis: None,
+            event_sink: None,
+        })
     }
 
-    /// Process frame with Waldo Vision's sophisticated multi-layer analysis and cooldown logic
-    pub fn process_frame(&mut self, frame_b64: String, _timestamp_ms: u64) -> PyResult<(bool, f32, usize)> {
-        // Get current time for cooldown calculation
-        let current_time = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_secs_f64();
+    /// Options in effect: those the detector was created with, plus the overrides of the
+    /// active `sensitivity_schedule` profile
+    pub fn config(&self) -> &DetectorConfig {
+        &self.config
+    }
 
-        // Convert base64 to raw image buffer with actual dimensions
-        let (frame_data, actual_width, actual_height) = self.decode_frame(&frame_b64)
-            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Decode error: {}", e)))?;
-        
-        // Initialize pipeline with actual frame dimensions if not done yet
-        if self.pipeline.is_none() {
-            let mut config = self.config_template.clone();
-            config.image_width = actual_width;
-            config.image_height = actual_height;
-            self.pipeline = Some(VisionPipeline::new(config));
+    /// Name of the `sensitivity_schedule` profile in effect; None outside every window or
+    /// without a schedule (see README "Sensitivity Schedule")
+    pub fn active_profile(&self) -> Option<&str> {
+        self.active_profile.as_deref()
+    }
+
+    /// Detector label from the config, if any
+    pub fn name(&self) -> Option<&str> {
+        self.config.name.as_deref()
+    }
+
+    /// Attach a base64 PNG thumbnail, fit within `max_dim` pixels, to triggering results;
+    /// `None` turns thumbnails off (see README "Trigger Thumbnails")
+    pub fn emit_thumbnail_on_trigger(&mut self, max_dim: Option<u32>) -> Result<(), DetectorError> {
+        if max_dim == Some(0) {
+            return Err(DetectorError::Config("max_dim must be at least 1 (use None to disable)".into()));
         }
+        self.config.thumbnail_max_dim = max_dim;
+        self.base_config.thumbnail_max_dim = max_dim;
+        Ok(())
+    }
 
-        // Process through Waldo Vision's multi-layer pipeline
-        let analysis = self.pipeline.as_mut().unwrap().process_frame(&frame_data);
-        self.frame_count += 1;
+    /// Snapshot of the detector's identity, progress and trigger counters
+    pub fn status(&self) -> DetectorStatus {
+        DetectorStatus {
+            name: self.config.name.clone(),
+            frame_count: self.frame_count,
+            scene_state: self.scene_state(),
+            frame_size: self.source_size,
+            roi: self.roi,
+            disturbed_triggers: self.disturbed_triggers,
+            volatile_triggers: self.volatile_triggers,
+            edge_triggers: self.edge_triggers,
+            suppressed_triggers: self.suppressed_triggers,
+            truncated_frames: self.truncated_frames,
+        }
+    }
 
-        // Determine action based on scene state and cooldown rules
-        let scene_state_str = match analysis.scene_state {
-            waldo_vision::pipeline::SceneState::Calibrating => "CALIBRATING",
-            waldo_vision::pipeline::SceneState::Stable => "STABLE",
-            waldo_vision::pipeline::SceneState::Volatile => "VOLATILE",
-            waldo_vision::pipeline::SceneState::Disturbed => "DISTURBED",
+    /// Counters and current scene state in OpenMetrics text format, labelled with the
+    /// detector `name` (see README "Metrics")
+    pub fn metrics_text(&self) -> String {
+        let labels = match &self.config.name {
+            Some(name) => {
+                let escaped = name.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n");
+                format!("detector=\"{}\"", escaped)
+            }
+            None => String::new(),
         };
-        
-        let (should_trigger, confidence) = match analysis.scene_state {
-            // Calibrating or Stable: Don't trigger Gemini
-            waldo_vision::pipeline::SceneState::Calibrating => (false, 0.0),
-            waldo_vision::pipeline::SceneState::Stable => (false, 0.0),
-            
-            // Volatile: IGNORE - only trigger on truly significant DISTURBED events
-            waldo_vision::pipeline::SceneState::Volatile => (false, 0.0),
-            
-            // Disturbed: Trigger with 0.25-second cooldown (new actors/actions)
-            waldo_vision::pipeline::SceneState::Disturbed => {
-                let disturbed_cooldown = 0.25; // Quarter second - urgent!
-                let time_since_last = current_time - self.last_disturbed_trigger;
-                
-                if time_since_last >= disturbed_cooldown {
-                    self.last_disturbed_trigger = current_time;
-                    
-                    // Calculate high confidence based on significance
-                    let base_confidence = 95.0;
-                    let significance_bonus = match analysis.report {
-                        Report::SignificantMention(mention_data) => {
-                            (mention_data.new_significant_moments.len() + 
-                             mention_data.completed_significant_moments.len()) as f32 * 5.0
-                        },
-                        _ => 0.0
-                    };
-                    
-                    (true, (base_confidence + significance_bonus).min(100.0))
-                } else {
-                    (false, 0.0) // Still in cooldown
-                }
+        let mut text = String::new();
+        let counters = [
+            ("frames_processed", "Frames analyzed", self.frame_count),
+            ("triggers", "Frames that set should_trigger", self.trigger_count),
+            ("disturbed_triggers", "Triggers from the Disturbed path", self.disturbed_triggers),
+            ("volatile_triggers", "Triggers from sustained Volatile escalation", self.volatile_triggers),
+            ("edge_triggers", "Triggers from edge mode activity onsets", self.edge_triggers),
+            ("suppressed_triggers", "Trigger-eligible frames held back by a cooldown", self.suppressed_triggers),
+            ("frames_skipped", "Uniform frames skipped by UniformFramePolicy.Ignore", self.skipped_frames),
+            ("decode_errors", "Frames rejected with DecodeError", self.decode_errors),
+            ("truncated_frames", "JPEGs missing their end-of-image marker", self.truncated_frames),
+            ("decode_cache_hits", "Decode cache hits", self.decode_cache_hits),
+            ("decode_cache_misses", "Decode cache misses", self.decode_cache_misses),
+            ("turbojpeg_frames", "Frames decoded by libjpeg-turbo", self.turbojpeg_frames),
+        ];
+        for (metric, help, value) in counters {
+            let name = format!("frame_change_detector_{}", metric);
+            let sample_labels = if labels.is_empty() { String::new() } else { format!("{{{}}}", labels) };
+            text.push_str(&format!("# TYPE {} counter\n# HELP {} {}.\n{}_total{} {}\n", name, name, help, name, sample_labels, value));
+        }
+        let name = "frame_change_detector_scene_state";
+        text.push_str(&format!("# TYPE {} stateset\n# HELP {} Scene state of the latest analyzed frame.\n", name, name));
+        let current = self.scene_state();
+        for state in ["CALIBRATING", "STABLE", "VOLATILE", "DISTURBED"] {
+            let separator = if labels.is_empty() { "" } else { "," };
+            text.push_str(&format!("{}{{{}{}{}=\"{}\"}} {}\n", name, labels, separator, name, state, (current == Some(state)) as u8));
+        }
+        text.push_str("# EOF\n");
+        text
+    }
+
+    /// Frames processed so far
+    pub fn frame_count(&self) -> u64 {
+        self.frame_count
+    }
+
+    /// Scene state of the latest analyzed frame (CALIBRATING / STABLE / VOLATILE / DISTURBED);
+    /// None before the first frame
+    pub fn scene_state(&self) -> Option<&'static str> {
+        self.previous_scene_state.as_ref().map(Self::scene_state_name)
+    }
+
+    /// Effective JPEG decoder (TurboJpeg falls back to Image if not compiled in)
+    pub fn decoder_backend(&self) -> DecoderBackend {
+        self.decoder_backend
+    }
+
+    /// Whether `close()` was called
+    pub fn is_closed(&self) -> bool {
+        self.closed
+    }
+
+    /// Whether a pipeline panic with `poison_on_panic` is refusing frames until `reset()`
+    pub fn is_poisoned(&self) -> bool {
+        self.poisoned
+    }
+
+    /// Decode a base64-encoded image (JPEG, PNG, ...) and analyze it
+    pub fn analyze_frame(&mut self, frame_b64: &str, timestamp_ms: u64) -> Result<FrameAnalysis, DetectorError> {
+        self.ensure_open()?;
+        self.begin_stage_profile();
+        // Convert base64 to raw image buffer with actual dimensions
+        let decoded = match self.decode_frame_cached(frame_b64) {
+            Ok(decoded) => decoded,
+            Err(e) => {
+                self.stage_profile = None;
+                self.decode_errors += 1;
+                return Err(DetectorError::Decode(format!("Decode error: {}", e)));
             }
         };
+        self.run_decoded(decoded, timestamp_ms)
+    }
 
-        // Return: (trigger_ai, confidence_score, tracked_objects_count, scene_state)
-        // Note: We'll need to modify the return signature to include scene state
-        Ok((should_trigger, confidence, analysis.tracked_blobs.len()))
+    /// `analyze_frame` for batch processing: also keeps the result in the recording window
+    /// (see README "Recorded Results")
+    pub fn analyze_frame_recorded(&mut self, frame_b64: &str, timestamp_ms: u64) -> Result<FrameAnalysis, DetectorError> {
+        let analysis = self.analyze_frame(frame_b64, timestamp_ms)?;
+        self.record_analysis(&analysis);
+        Ok(analysis)
     }
 
-    /// Configure Waldo Vision pipeline (simplified interface)
-    pub fn configure(&mut self, 
-                    _buffer_duration_ms: Option<u64>,
-                    _change_threshold: Option<f32>,
-                    _frame_interval_ms: Option<u64>) -> PyResult<()> {
-        // Note: Waldo Vision pipeline would need to be recreated for config changes
-        // For now, store the values for future pipeline recreation
-        Ok(())
+    /// Analyze an encoded image (JPEG, PNG, ...) that is already in memory, without base64
+    pub fn analyze_image_bytes(&mut self, data: &[u8], timestamp_ms: u64) -> Result<FrameAnalysis, DetectorError> {
+        self.ensure_open()?;
+        self.run_encoded(data, timestamp_ms, None)
+    }
+
+    /// Read an encoded image from disk and analyze it (see README "Image Files")
+    pub fn analyze_image_file(&mut self, path: &std::path::Path, timestamp_ms: u64) -> Result<FrameAnalysis, DetectorError> {
+        self.ensure_open()?;
+        let data = std::fs::read(path).map_err(|source| DetectorError::Io { path: path.to_path_buf(), source })?;
+        self.run_encoded(&data, timestamp_ms, Some(path))
+    }
+
+    /// Analyze a raw 8-bit grayscale frame (row-major, one byte per pixel) without decoding;
+    /// see README "Raw Pixel Input"
+    pub fn analyze_pixels(&mut self, pixels: Vec<u8>, width: u32, height: u32, timestamp_ms: u64) -> Result<FrameAnalysis, DetectorError> {
+        self.ensure_open()?;
+        if width == 0 || height == 0 {
+            return Err(DetectorError::DimensionMismatch(format!("Frame dimensions must be non-zero, got {}x{}", width, height)));
+        }
+        let expected = width as usize * height as usize;
+        if pixels.len() != expected {
+            return Err(DetectorError::DimensionMismatch(format!(
+                "Expected {} pixels for a {}x{} frame, got {}",
+                expected, width, height, pixels.len()
+            )));
+        }
+        self.run_decoded(DecodedFrame { luma: pixels, chroma: None, width, height }, timestamp_ms)
+    }
+
+    /// Analyze an interleaved 8-bit RGB frame (`width * height * 3` bytes), converted to luma
+    /// with `luma_coefficients`; see README "RGB Input"
+    pub fn analyze_rgb(&mut self, rgb: &[u8], width: u32, height: u32, timestamp_ms: u64) -> Result<FrameAnalysis, DetectorError> {
+        self.ensure_open()?;
+        if width == 0 || height == 0 {
+            return Err(DetectorError::DimensionMismatch(format!("Frame dimensions must be non-zero, got {}x{}", width, height)));
+        }
+        let expected = width as usize * height as usize * 3;
+        if rgb.len() != expected {
+            return Err(DetectorError::DimensionMismatch(format!(
+                "Expected {} bytes for a {}x{} RGB frame, got {}",
+                expected, width, height, rgb.len()
+            )));
+        }
+        self.begin_stage_profile();
+        let started = self.stage_start();
+        let luma = Self::rgb_to_luma(rgb, self.config.luma_coefficients);
+        let chroma = self.config.multi_channel.then(|| Self::chroma_planes(rgb));
+        self.record_stage("grayscale", started);
+        self.color_converted_frames += 1;
+        self.run_decoded(DecodedFrame { luma, chroma, width, height }, timestamp_ms)
+    }
+
+    /// Analyze a base64 frame and return only its scene-state code (0=Calibrating, 1=Stable,
+    /// 2=Volatile, 3=Disturbed). Skips triggering, blobs and the result; see README "Quick State".
+    pub fn quick_state(&mut self, frame_b64: &str, timestamp_ms: u64) -> Result<u8, DetectorError> {
+        self.ensure_open()?;
+        let decoded = self.decode_frame_cached(frame_b64).map_err(|e| {
+            self.decode_errors += 1;
+            DetectorError::Decode(format!("Decode error: {}", e))
+        })?;
+        self.analyze_decoded(decoded, timestamp_ms, true)?;
+        Ok(self.previous_scene_state.as_ref().map_or(0, Self::scene_state_rank))
+    }
+
+    /// Recorded batch result for `frame_count == index` (see README "Recorded Results");
+    /// None when recording is off, the frame was evicted or never recorded
+    pub fn recorded_analysis(&self, index: u64) -> Option<&FrameAnalysis> {
+        self.recorded_analyses.binary_search_by_key(&index, |analysis| analysis.frame_count)
+            .ok()
+            .map(|position| &self.recorded_analyses[position])
+    }
+
+    /// (first, last) recorded frame_count, or None when nothing is recorded
+    pub fn recorded_range(&self) -> Option<(u64, u64)> {
+        Some((self.recorded_analyses.front()?.frame_count, self.recorded_analyses.back()?.frame_count))
+    }
+
+    /// Take every queued trigger, oldest first (see README "Trigger Queue"). With
+    /// `debounce_ms`, a trigger at most that long after the previous one joins its event,
+    /// which keeps the highest-confidence trigger and spans all of their timestamps.
+    pub fn drain_triggers(&mut self, debounce_ms: Option<u64>) -> Vec<TriggerEvent> {
+        let mut events: Vec<TriggerEvent> = Vec::new();
+        for trigger in self.trigger_queue.drain(..) {
+            if let (Some(window), Some(event)) = (debounce_ms, events.last_mut()) {
+                if trigger.timestamp_ms.saturating_sub(event.last_timestamp_ms) <= window {
+                    event.first_timestamp_ms = event.first_timestamp_ms.min(trigger.timestamp_ms);
+                    event.last_timestamp_ms = event.last_timestamp_ms.max(trigger.timestamp_ms);
+                    let coalesced_count = event.coalesced_count + 1;
+                    // Ties keep the earlier trigger
+                    if trigger.confidence > event.confidence {
+                        *event = TriggerEvent { first_timestamp_ms: event.first_timestamp_ms, last_timestamp_ms: event.last_timestamp_ms, ..trigger };
+                    }
+                    event.coalesced_count = coalesced_count;
+                    continue;
+                }
+            }
+            events.push(trigger);
+        }
+        events
     }
 
-    /// Get current configuration and processing stats
-    pub fn get_config(&self) -> PyResult<(u64, f32, u64)> {
-        Ok((
-            self.frame_count,           // Total frames processed by Waldo Vision
-            5.0,                        // Default change threshold
-            33,                         // 30fps interval (480p smooth)
-        ))
+    /// Release the pipelines and caches; any later frame processing fails with
+    /// `DetectorError::Closed`. Idempotent. Introspection methods keep working.
+    pub fn close(&mut self) {
+        // Last chance to deliver buffered events, backoff or not; the connection is closed either way
+        if let Some(sink) = &mut self.event_sink {
+            sink.flush_final();
+        }
+        self.event_sink = None;
+        self.closed = true;
+        self.pipeline = None;
+        self.chroma_pipelines = Vec::new();
+        self.decode_cache = std::collections::VecDeque::new();
+        self.previous_blobs = Vec::new();
     }
 
     /// Reset Waldo Vision pipeline state
-    pub fn reset(&mut self) -> PyResult<()> {
+    pub fn reset(&mut self) {
+        // A panicked pipeline's state is suspect; rebuild it from scratch
+        if self.poisoned {
+            self.poisoned = false;
+            self.invalidate_pipeline();
+        }
         // Reset counters and cooldowns
+        self.hotspots.clear();
+        self.last_report = None;
         self.frame_count = 0;
-        self.last_volatile_trigger = 0.0;
-        self.last_disturbed_trigger = 0.0;
-        Ok(())
+        self.last_volatile_trigger = None;
+        self.last_disturbed_trigger = None;
+        self.last_calm = None;
+        for region in &mut self.regions {
+            region.last_trigger = None;
+        }
+        self.motionless_run = 0;
+        self.disturbance_hold_remaining = 0;
+        self.spike_promoted = false;
+        self.volatile_run = 0;
+        self.unstable_run = 0;
+        self.grayscale_fast_path_frames = 0;
+        self.color_converted_frames = 0;
+        self.turbojpeg_frames = 0;
+        self.decode_cache_hits = 0;
+        self.decode_cache_misses = 0;
+        self.trigger_count = 0;
+        self.last_trigger_time = None;
+        self.skipped_frames = 0;
+        self.decode_errors = 0;
+        self.truncated_frames = 0;
+        self.disturbed_triggers = 0;
+        self.volatile_triggers = 0;
+        self.edge_triggers = 0;
+        self.suppressed_triggers = 0;
+        self.recorded_analyses.clear();
+        self.trigger_queue.clear();
+        self.last_analysis = None;
+        self.clear_temporal_average();
+        self.timing = TimingDiagnostics::new(self.config.low_memory);
+        self.previous_scene_state = None;
+        self.last_trigger_eligible = false;
+        self.confidence_ema = None;
     }
 
-    /// Get Waldo Vision scene analysis state
-    pub fn get_analysis_info(&self) -> PyResult<(String, u64)> {
-        Ok((
-            "Waldo Vision Multi-Layer Analysis Active".to_string(),
-            self.frame_count
-        ))
+    /// Fully resolved Waldo Vision `PipelineConfig` (thresholds already converted from
+    /// percent) and where it came from: the created pipeline once the first frame locked in
+    /// the image dimensions (`"pipeline"`), else the template (`"template"`)
+    pub fn effective_pipeline_config(&self) -> (&PipelineConfig, &'static str) {
+        match &self.pipeline_config {
+            Some(config) => (config, "pipeline"),
+            None => (&self.config_template, "template"),
+        }
     }
 
-    /// Get current scene state and cooldown status
-    pub fn get_scene_status(&self) -> PyResult<(String, f64, f64)> {
-        let current_time = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_secs_f64();
-            
-        let volatile_cooldown_remaining = (self.last_volatile_trigger + 1.0 - current_time).max(0.0);
-        let disturbed_cooldown_remaining = (self.last_disturbed_trigger + 0.25 - current_time).max(0.0);
-        
-        Ok((
-            "MONITORING".to_string(), // Scene state would need pipeline exposure
-            volatile_cooldown_remaining,
-            disturbed_cooldown_remaining
-        ))
+    /// Check a config without building a detector: every validation error at once, in field
+    /// order (see README "Config Validation")
+    pub fn validate_config(config: &DetectorConfig) -> Result<(), Vec<String>> {
+        let errors = config.validation_errors();
+        if errors.is_empty() { Ok(()) } else { Err(errors) }
     }
 
-    /// Process frame and return results with scene state for logging
-    pub fn process_frame_with_state(&mut self, frame_b64: String, timestamp_ms: u64) -> PyResult<(bool, f32, usize, String)> {
-        // Get current time for cooldown calculation
-        let current_time = std::time::SystemTime::now()
-            .duration_since(std::time::UNIX_EPOCH)
-            .unwrap_or_default()
-            .as_secs_f64();
-
-        // Convert base64 to raw image buffer with actual dimensions
-        let (frame_data, actual_width, actual_height) = self.decode_frame(&frame_b64)
-            .map_err(|e| PyErr::new::<pyo3::exceptions::PyValueError, _>(format!("Decode error: {}", e)))?;
-        
-        // Initialize pipeline with actual frame dimensions if not done yet
-        if self.pipeline.is_none() {
-            let mut config = self.config_template.clone();
-            config.image_width = actual_width;
-            config.image_height = actual_height;
-            self.pipeline = Some(VisionPipeline::new(config));
+    /// Suggest a chunk grid for a frame size: `(columns, rows, chunk_width, chunk_height)` with
+    /// chunks close to `target_chunk_pixels` in area, near-square, and preferably dividing the
+    /// frame evenly (see README "Grid Suggestions")
+    pub fn suggest_grid(width: u32, height: u32, target_chunk_pixels: u32) -> Result<(u32, u32, u32, u32), DetectorError> {
+        if width == 0 || height == 0 || target_chunk_pixels == 0 {
+            return Err(DetectorError::Config(format!(
+                "suggest_grid needs a non-zero frame size and chunk area, got {}x{} and {}",
+                width, height, target_chunk_pixels
+            )));
+        }
+        let target = target_chunk_pixels as f64;
+        let side = target.sqrt();
+        // Candidate sides from half to twice the ideal square side, never wider than the frame
+        let candidates = |limit: u32| {
+            let low = ((side / 2.0).floor() as u32).max(1).min(limit);
+            let high = ((side * 2.0).ceil() as u32).max(1).min(limit);
+            low..=high
+        };
+        let mut best = (f64::INFINITY, 1, 1);
+        for chunk_w in candidates(width) {
+            for chunk_h in candidates(height) {
+                // Log-distance from the target area and from square, plus the share of each
+                // axis left over past the last whole chunk
+                let cost = ((chunk_w * chunk_h) as f64 / target).ln().abs()
+                    + (chunk_w as f64 / chunk_h as f64).ln().abs()
+                    + (width % chunk_w) as f64 / width as f64
+                    + (height % chunk_h) as f64 / height as f64;
+                if cost < best.0 {
+                    best = (cost, chunk_w, chunk_h);
+                }
+            }
         }
+        let (_, chunk_w, chunk_h) = best;
+        Ok((width / chunk_w, height / chunk_h, chunk_w, chunk_h))
+    }
 
-        // Process through Waldo Vision's multi-layer pipeline
-        let analysis = self.pipeline.as_mut().unwrap().process_frame(&frame_data);
-        self.frame_count += 1;
+    /// Drop the learned background so the pipeline calibrates again from the next frame,
+    /// keeping counters and cooldowns; re-arms `alert_on_first_motion`
+    pub fn recalibrate(&mut self) {
+        self.invalidate_pipeline();
+    }
 
-        // Get scene state string
-        let scene_state_str = match analysis.scene_state {
-            waldo_vision::pipeline::SceneState::Calibrating => "CALIBRATING",
-            waldo_vision::pipeline::SceneState::Stable => "STABLE",
-            waldo_vision::pipeline::SceneState::Volatile => "VOLATILE",
-            waldo_vision::pipeline::SceneState::Disturbed => "DISTURBED",
-        };
-        
-        let (should_trigger, confidence) = match analysis.scene_state {
-            // Calibrating or Stable: Don't trigger Gemini
-            waldo_vision::pipeline::SceneState::Calibrating => (false, 0.0),
-            waldo_vision::pipeline::SceneState::Stable => (false, 0.0),
-            
-            // Volatile: IGNORE - only trigger on truly significant DISTURBED events
-            waldo_vision::pipeline::SceneState::Volatile => (false, 0.0),
-            
-            // Disturbed: Trigger with 0.25-second cooldown (new actors/actions)
-            waldo_vision::pipeline::SceneState::Disturbed => {
-                let disturbed_cooldown = 0.25; // Quarter second - urgent!
-                let time_since_last = current_time - self.last_disturbed_trigger;
-                
-                if time_since_last >= disturbed_cooldown {
-                    self.last_disturbed_trigger = current_time;
-                    
-                    // Calculate high confidence based on significance
-                    let base_confidence = 95.0;
-                    let significance_bonus = match analysis.report {
-                        Report::SignificantMention(mention_data) => {
-                            (mention_data.new_significant_moments.len() + 
-                             mention_data.completed_significant_moments.len()) as f32 * 5.0
-                        },
-                        _ => 0.0
-                    };
-                    
-                    (true, (base_confidence + significance_bonus).min(100.0))
-                } else {
-                    (false, 0.0) // Still in cooldown
+    /// Let the next Disturbed frame trigger immediately, without a full `reset()`
+    pub fn clear_disturbed_cooldown(&mut self) {
+        self.last_disturbed_trigger = None;
+    }
+
+    /// Let the next Volatile trigger fire immediately, without a full `reset()`
+    pub fn clear_volatile_cooldown(&mut self) {
+        self.last_volatile_trigger = None;
+    }
+
+    /// Seconds since the latest frame with `should_trigger` set, on the cooldown clock
+    /// (frame timestamps in deterministic mode); None if nothing has triggered yet
+    pub fn seconds_since_last_trigger(&self) -> Option<f64> {
+        self.last_trigger_time.map(|last| (self.clock_secs(None) - last).max(0.0))
+    }
+
+    /// Raw cooldown timers: (last_volatile_trigger, last_disturbed_trigger), in seconds on the
+    /// cooldown clock (frame timestamps in deterministic mode); None means the timer has
+    /// never fired or was cleared
+    pub fn cooldown_timers(&self) -> (Option<f64>, Option<f64>) {
+        (self.last_volatile_trigger, self.last_disturbed_trigger)
+    }
+
+    /// Blobs reported for the most recent frame (the same list as its `analysis.blobs`)
+    pub fn tracked_blobs(&self) -> Vec<BlobInfo> {
+        self.reported_blobs(self.previous_blobs.clone())
+    }
+
+    /// Waldo Vision's `Report` for the latest analyzed frame, as the pipeline produced it
+    /// (see README "Pipeline Reports"); None before the first frame, after a pipeline
+    /// rebuild or reset, and in low-memory mode
+    pub fn last_report(&self) -> Option<&Report> {
+        self.last_report.as_ref()
+    }
+
+    /// Up to `n` chunks of the latest frame that changed the most, largest change first (see
+    /// README "Hotspots"). Only chunks counted as moving are candidates, so a calm frame
+    /// returns fewer, or none.
+    pub fn hotspots(&self, n: usize) -> Vec<Hotspot> {
+        let geometry = self.geometry.unwrap_or(FrameGeometry::identity(0, 0));
+        let (chunk_w, chunk_h) = (self.config_template.chunk_width, self.config_template.chunk_height);
+        let grid_w = self.pipeline_size().map_or(1, |(width, _)| (width / chunk_w).max(1));
+        self.hotspots.iter().take(n).map(|&(index, disturbance)| {
+            let (column, row) = (index as u32 % grid_w, index as u32 / grid_w);
+            let (x, y) = ((column * chunk_w) as f64, (row * chunk_h) as f64);
+            let min = geometry.map_point(self.config.output_coords, x, y);
+            let max = geometry.map_point(self.config.output_coords, x + chunk_w as f64, y + chunk_h as f64);
+            let center = geometry.map_point(self.config.output_coords, x + chunk_w as f64 / 2.0, y + chunk_h as f64 / 2.0);
+            Hotspot { chunk: (column, row), center, bbox: (min.0, min.1, max.0, max.1), disturbance }
+        }).collect()
+    }
+
+    /// Whether the latest frame met the trigger conditions, cooldowns aside; false before
+    /// any frame. Reads the cached result only, so it never touches the cooldown timers
+    pub fn would_trigger_ignoring_cooldown(&self) -> bool {
+        self.last_trigger_eligible
+    }
+
+    /// Seed a fresh pipeline with a known-clean background so live frames are analyzed
+    /// immediately instead of after calibration (see README "Reference Frames").
+    /// The frame must match the locked frame size; mid-stream, it replaces the pipeline.
+    pub fn set_reference_frame(&mut self, frame_b64: &str) -> Result<(), DetectorError> {
+        self.ensure_open()?;
+        let decoded = self.decode_frame(frame_b64)
+            .map_err(|e| DetectorError::Decode(format!("Decode error: {}", e)))?;
+        self.check_seed_size(&decoded, "Reference frame")?;
+        self.seed_pipeline(vec![decoded])
+    }
+
+    /// Seed a fresh pipeline with several frames of the normal scene, so calibration learns
+    /// its usual variation (see README "Seeded Calibration"). All frames must share the
+    /// locked frame size; mid-stream, it replaces the pipeline.
+    pub fn seed_calibration(&mut self, frames: &[impl AsRef<str>]) -> Result<(), DetectorError> {
+        self.ensure_open()?;
+        if frames.is_empty() {
+            return Err(DetectorError::Config("seed_calibration needs at least one frame".into()));
+        }
+        // Decode and size-check everything first, so a bad frame leaves the stream intact
+        let mut decoded_frames = Vec::with_capacity(frames.len());
+        for (index, frame_b64) in frames.iter().enumerate() {
+            let decoded = self.decode_frame(frame_b64.as_ref())
+                .map_err(|e| DetectorError::Decode(format!("Decode error in seed frame {}: {}", index, e)))?;
+            if let Some(first) = decoded_frames.first() {
+                let expected = Self::rotated_size(first, self.config.rotation);
+                let (width, height) = Self::rotated_size(&decoded, self.config.rotation);
+                if (width, height) != expected {
+                    return Err(DetectorError::DimensionMismatch(format!(
+                        "Seed frame {} is {}x{} but seed frame 0 is {}x{}",
+                        index, width, height, expected.0, expected.1
+                    )));
                 }
+            } else {
+                self.check_seed_size(&decoded, "Seed frame 0")?;
             }
+            decoded_frames.push(decoded);
+        }
+        self.seed_pipeline(decoded_frames)
+    }
+
+    /// Restrict analysis to a rectangle of decoded-frame pixels (see README "Region of Interest").
+    /// Clipped to the frame once its size is known; before the first frame, validation is
+    /// deferred until it arrives.
+    pub fn set_roi(&mut self, x: i64, y: i64, width: u32, height: u32) -> Result<(), DetectorError> {
+        if width == 0 || height == 0 {
+            return Err(DetectorError::Config(format!("ROI must have a non-zero size, got {}x{}", width, height)));
+        }
+        self.require_step(PreprocessStep::Crop, "set_roi")?;
+        let Some((frame_width, frame_height)) = self.source_size else {
+            self.pending_roi = Some((x, y, width, height));
+            return Ok(());
         };
+        let roi = self.clip_roi((x, y, width, height), frame_width, frame_height).ok_or_else(|| {
+            DetectorError::Config(format!(
+                "ROI ({}, {}, {}, {}) lies entirely outside the {}x{} frame",
+                x, y, width, height, frame_width, frame_height
+            ))
+        })?;
+        self.apply_roi(Some(roi));
+        Ok(())
+    }
 
-        // Return: (trigger_ai, confidence_score, tracked_objects_count, scene_state)
-        Ok((should_trigger, confidence, analysis.tracked_blobs.len(), scene_state_str.to_string()))
+    /// Analyze the whole frame again
+    pub fn clear_roi(&mut self) {
+        self.pending_roi = None;
+        self.apply_roi(None);
     }
-}
 
-impl FrameChangeDetector {
-    /// Decode base64 JPEG to raw grayscale buffer for Waldo Vision
-    fn decode_frame(&self, frame_b64: &str) -> Result<(Vec<u8>, u32, u32), String> {
-        use base64::{Engine as _, engine::general_purpose::STANDARD};
-        
-        // Decode base64 using new API
-        let img_data = STANDARD.decode(frame_b64)
-            .map_err(|e| format!("Base64 decode error: {}", e))?;
-        
-        // Load image and convert to grayscale for Waldo Vision
-        let img = image::load_from_memory(&img_data)
-            .map_err(|e| format!("Image load error: {}", e))?;
-        let gray_img = img.to_luma8();
-        let (width, height) = gray_img.dimensions();
-        
-        // Return pixels with actual dimensions
-        Ok((gray_img.into_raw(), width, height))
+    /// Effective (clipped) ROI as (x, y, width, height); None when unset or still deferred
+    pub fn roi(&self) -> Option<(u32, u32, u32, u32)> {
+        self.roi
+    }
+
+    /// ROI passed to `set_roi` before the first frame, as given (unclipped); None once the
+    /// frame size is known and it has been applied
+    pub fn pending_roi(&self) -> Option<(i64, i64, u32, u32)> {
+        self.pending_roi
+    }
+
+    /// Exclude a polygon (vertices in decoded-frame pixels) from analysis; its pixels are
+    /// zeroed before the pipeline sees them. Several polygons combine into one mask.
+    pub fn add_exclusion_polygon(&mut self, points: Vec<(u32, u32)>) -> Result<(), DetectorError> {
+        if points.len() < 3 {
+            return Err(DetectorError::Config(format!("Exclusion polygon needs at least 3 points, got {}", points.len())));
+        }
+        self.require_step(PreprocessStep::Mask, "add_exclusion_polygon")?;
+        self.exclusion_polygons.push(points);
+        self.exclusion_mask = None;
+        self.invalidate_pipeline();
+        Ok(())
+    }
+
+    /// `DetectorError::Config` when `preprocessing` leaves out the step `method` configures
+    fn require_step(&self, step: PreprocessStep, method: &str) -> Result<(), DetectorError> {
+        if self.config.preprocessing_steps().contains(&step) {
+            return Ok(());
+        }
+        Err(DetectorError::Config(format!("{} needs {:?} in preprocessing, which leaves it out", method, step)))
+    }
+
+    /// The preprocessing steps that currently do something, in the order they run, each with
+    /// a summary of its parameters (see README "Preprocessing Chain")
+    pub fn preprocessing_chain(&self) -> Vec<(PreprocessStep, String)> {
+        self.config.preprocessing_steps().into_iter().filter_map(|step| {
+            let parameters = match step {
+                PreprocessStep::Rotate => format!("{:?}", self.config.rotation?),
+                PreprocessStep::ExposureCompensation => match self.exposure_reference {
+                    Some(mean) if self.config.exposure_compensation => format!("reference mean {:.1}", mean),
+                    _ if self.config.exposure_compensation => "reference set by the next pipeline's first frame".to_string(),
+                    _ => return None,
+                },
+                PreprocessStep::Mask => match self.exclusion_polygons.len() {
+                    0 => return None,
+                    1 => "1 polygon".to_string(),
+                    count => format!("{} polygons", count),
+                },
+                PreprocessStep::Crop => match (self.roi, self.pending_roi) {
+                    (Some((x, y, width, height)), _) => format!("{}x{} at ({}, {})", width, height, x, y),
+                    (None, Some((x, y, width, height))) => format!("{}x{} at ({}, {}), clipped on the first frame", width, height, x, y),
+                    (None, None) => return None,
+                },
+                PreprocessStep::Upscale => format!("min_analysis_dim={}", self.config.min_analysis_dim?),
+                PreprocessStep::Subsample if self.config.chunk_subsample > 1 => format!("chunk_subsample={}", self.config.chunk_subsample),
+                PreprocessStep::Subsample => return None,
+                PreprocessStep::Equalize => match self.config.equalization {
+                    Equalization::Off => return None,
+                    Equalization::Global => "Global".to_string(),
+                    Equalization::Clahe => format!("Clahe, {}-pixel tiles", self.config.clahe_tile_size),
+                },
+                PreprocessStep::TemporalAverage => format!("{} frames", self.config.temporal_average_frames?),
+            };
+            Some((step, parameters))
+        }).collect()
     }
-}
 
-/// Python module
-#[pymodule]
-fn frame_change_detector(_py: Python, m: &PyModule) -> PyResult<()> {
-    m.add_class::<FrameChangeDetector>()?;
-    Ok(())
-}
\ No newline at end of file
+    /// Push trigger events to the Unix domain socket at `path` as JSON lines, replacing any
+    /// earlier sink; None disconnects. Fails with `DetectorError::EventSink` if the socket
+    /// cannot be connected now and with `DetectorError::Closed` after `close()` (see README
+    /// "Event Sink")
+    pub fn set_event_sink(&mut self, path: Option<&std::path::Path>) -> Result<(), DetectorError> {
+        self.ensure_open()?;
+        self.event_sink = match path {
+            Some(path) => Some(EventSink::connect(path).map_err(|source| DetectorError::EventSink { path: path.to_path_buf(), source })?),
+            None => None,
+        };
+        Ok(())
+    }
+
+    /// Delivery counters of the event sink; None without one
+    pub fn event_sink_stats(&self) -> Option<EventSinkStats> {
+        self.event_sink.as_ref().map(EventSink::stats)
+    }
+
+    /// Remove every exclusion polygon
+    pub fn clear_exclusion_polygons(&mut self) {
+        if !self.exclusion_polygons.is_empty() {
+            self.exclusion_polygons.clear();
+            self.exclusion_mask = None;
+            self.invalidate_pipeline();
+        }
+    }
+
+    /// Exclusion polygons in insertion order
+    pub fn exclusion_polygons(&self) -> &[Vec<(u32, u32)>] {
+        &self.exclusion_polygons
+    }
+
+    /// Add (or replace) a named region, in decoded-frame pixels, with its own trigger cooldown
+    pub fn add_region(&mut self, name: impl Into<String>, x: u32, y: u32, width: u32, height: u32) -> Result<(), DetectorError> {
+        let name = name.into();
+        if width == 0 || height == 0 {
+            return Err(DetectorError::Config(format!("Region '{}' must have a non-zero size", name)));
+        }
+        self.regions.retain(|region| region.name != name);
+        self.regions.push(Region { name, rect: (x, y, width, height), last_trigger: None });
+        Ok(())
+    }
+
+    /// Remove a named region; returns whether it existed
+    pub fn remove_region(&mut self, name: &str) -> bool {
+        let before = self.regions.len();
+        self.regions.retain(|region| region.name != name);
+        self.regions.len() != before
+    }
+
+    /// Remaining cooldown (seconds) for every region, keyed by name
+    pub fn region_cooldowns(&self) -> std::collections::BTreeMap<String, f64> {
+        let current_time = self.clock_secs(None);
+        self.regions.iter()
+            .map(|region| (region.name.clone(), cooldown_remaining(region.last_trigger, REGION_COOLDOWN_SECS, current_time)))
+            .collect()
+    }
+
+    /// Decode cache counters: (hits, misses, cached_frames); all 0 while the cache is off
+    pub fn decode_cache_stats(&self) -> (u64, u64, usize) {
+        (self.decode_cache_hits, self.decode_cache_misses, self.decode_cache.len())
+    }
+
+    /// Decode path counters: (grayscale_fast_path_frames, color_converted_frames), by whether
+    /// the source was single-channel, whichever backend decoded it
+    pub fn decode_stats(&self) -> (u64, u64) {
+        (self.grayscale_fast_path_frames, self.color_converted_frames)
+    }
+
+    /// What the pipeline currently takes as background: each chunk filled with its mean luma
+    /// over the pipeline's chunk history, as a base64 grayscale PNG in analysis coordinates.
+    /// A debugging aid (see README "Background Model"); None before the first analyzed
+    /// frame and in low-memory mode.
+    pub fn export_background_frame(&self) -> Option<String> {
+        let config = self.pipeline_config.as_ref()?;
+        let frames = self.background_history.len();
+        let (chunk_w, chunk_h) = (config.chunk_width, config.chunk_height);
+        let (grid_w, grid_h) = (config.image_width / chunk_w, config.image_height / chunk_h);
+        if self.pipeline.is_none() || frames == 0 || grid_w == 0 || grid_h == 0 {
+            return None;
+        }
+        let mut background = vec![0.0; self.background_history[0].len()];
+        for means in &self.background_history {
+            for (sum, mean) in background.iter_mut().zip(means) {
+                *sum += mean / frames as f64;
+            }
+        }
+        let image = image::GrayImage::from_fn(grid_w * chunk_w, grid_h * chunk_h, |x, y| {
+            let mean = background.get(((y / chunk_h) * grid_w + x / chunk_w) as usize).copied().unwrap_or(0.0);
+            image::Luma([mean.round() as u8])
+        });
+        self.png_b64(image, "Background frame")
+    }
+
+    /// Timestamp diagnostics: out-of-order/duplicate counts and min/max forward delta (ms)
+    pub fn timing_diagnostics(&self) -> TimingReport {
+        TimingReport {
+            out_of_order_frames: self.timing.out_of_order_frames,
+            duplicate_timestamps: self.timing.duplicate_timestamps,
+            skipped_duplicates: self.timing.skipped_duplicates,
+            min_delta_ms: self.timing.min_delta_ms,
+            max_delta_ms: self.timing.max_delta_ms,
+            last_timestamp_ms: self.timing.last_timestamp_ms,
+            estimated_fps: self.timing.estimated_fps(),
+        }
+    }
+
+    /// Source frame rate from the rolling median of recent timestamp deltas (see README
+    /// "Frame Rate Estimation"); None until a few frames arrive, and in low-memory mode
+    pub fn estimated_fps(&self) -> Option<f32> {
+        self.timing.estimated_fps()
+    }
+}
+
+impl CoreDetector {
+    /// "[name] " prefix for log messages, so output from many detectors stays distinguishable
+    fn log_prefix(&self) -> String {
+        self.config.name.as_ref().map_or_else(String::new, |name| format!("[{}] ", name))
+    }
+
+    /// Dimensions the pipeline was initialized with, once the first frame has arrived
+    fn pipeline_size(&self) -> Option<(u32, u32)> {
+        self.pipeline_config.as_ref().map(|config| (config.image_width, config.image_height))
+    }
+
+    /// Shared per-frame path for encoded (not base64) input: decode, then analyze
+    fn run_encoded(&mut self, data: &[u8], timestamp_ms: u64, path: Option<&std::path::Path>) -> Result<FrameAnalysis, DetectorError> {
+        self.begin_stage_profile();
+        let decoded = match self.decode_image_bytes(data) {
+            Ok(decoded) => decoded,
+            Err(e) => {
+                self.stage_profile = None;
+                self.decode_errors += 1;
+                return Err(DetectorError::Decode(match path {
+                    Some(path) => format!("Decode error in '{}': {}", path.display(), e),
+                    None => format!("Decode error: {}", e),
+                }));
+            }
+        };
+        self.run_decoded(decoded, timestamp_ms)
+    }
+
+    /// Start timing a frame's stages when profiling (no-op if one is already in flight)
+    fn begin_stage_profile(&mut self) {
+        if self.config.profile && self.stage_profile.is_none() {
+            self.stage_profile = Some(StageProfile { started: Instant::now(), stages_ms: Default::default() });
+        }
+    }
+
+    /// Start of a stage; None (no clock read) unless a frame is being profiled
+    fn stage_start(&self) -> Option<Instant> {
+        self.stage_profile.as_ref().map(|_| Instant::now())
+    }
+
+    /// Add the time since `started` to `stage` of the frame being profiled
+    fn record_stage(&mut self, stage: &str, started: Option<Instant>) {
+        if let (Some(profile), Some(started)) = (self.stage_profile.as_mut(), started) {
+            *profile.stages_ms.entry(stage.to_string()).or_default() += started.elapsed().as_secs_f64() * 1000.0;
+        }
+    }
+
+    /// Switch to the `sensitivity_schedule` profile active at this frame's time. A switch
+    /// rebuilds the pipeline, since Waldo reads `change_threshold` only when one is created.
+    fn apply_schedule(&mut self, timestamp_ms: u64) {
+        let Some(schedule) = &self.base_config.sensitivity_schedule else {
+            return;
+        };
+        let epoch_ms = if schedule.wall_clock {
+            std::time::SystemTime::now().duration_since(std::time::UNIX_EPOCH).unwrap_or_default().as_millis() as u64
+        } else {
+            timestamp_ms
+        };
+        let profile = schedule.active_profile(epoch_ms);
+        if profile.map(|profile| &profile.name) == self.active_profile.as_ref() {
+            return;
+        }
+        log::info!(
+            "{}Switching to sensitivity profile {} (was {}); the pipeline recalibrates",
+            self.log_prefix(), profile.map_or("<base>", |profile| &profile.name),
+            self.active_profile.as_deref().unwrap_or("<base>")
+        );
+        let config = self.base_config.with_profile(profile);
+        self.active_profile = profile.map(|profile| profile.name.clone());
+        self.config_template.behavioral_anomaly_threshold = config.change_threshold as f64 / 100.0;
+        self.config = config;
+        self.invalidate_pipeline();
+    }
+
+    /// Cooldown clock in seconds: UNIX-epoch wall time, or in deterministic mode the frame
+    /// timestamp (the latest observed one when called outside frame processing)
+    fn clock_secs(&self, frame_timestamp_ms: Option<u64>) -> f64 {
+        if self.config.deterministic {
+            let timestamp_ms = frame_timestamp_ms.or(self.timing.last_timestamp_ms).unwrap_or(0);
+            return timestamp_ms as f64 / 1000.0;
+        }
+        std::time::SystemTime::now()
+            .duration_since(std::time::UNIX_EPOCH)
+            .unwrap_or_default()
+            .as_secs_f64()
+    }
+
+    /// Keep a batch result in the recording window, oldest evicted first. Thumbnails are
+    /// dropped, and frames that did not advance `frame_count` (ignored uniform frames) are skipped.
+    fn record_analysis(&mut self, analysis: &FrameAnalysis) {
+        let Some(capacity) = self.config.record_batch_results.filter(|_| !self.config.low_memory) else {
+            return;
+        };
+        if self.recorded_analyses.back().is_some_and(|last| last.frame_count >= analysis.frame_count) {
+            return;
+        }
+        if self.recorded_analyses.len() >= capacity {
+            self.recorded_analyses.pop_front();
+        }
+        self.recorded_analyses.push_back(FrameAnalysis { thumbnail_png_b64: None, ..analysis.clone() });
+    }
+
+    /// Queue a fired trigger for `drain_triggers`, evicting the oldest once the queue is full
+    fn queue_trigger(&mut self, timestamp_ms: u64, confidence: f32, trigger_reason: Option<String>) {
+        let Some(capacity) = self.config.trigger_queue_size else {
+            return;
+        };
+        if self.trigger_queue.len() >= capacity {
+            self.trigger_queue.pop_front();
+        }
+        self.trigger_queue.push_back(TriggerEvent {
+            frame_count: self.frame_count,
+            timestamp_ms,
+            confidence,
+            trigger_reason,
+            first_timestamp_ms: timestamp_ms,
+            last_timestamp_ms: timestamp_ms,
+            coalesced_count: 1,
+        });
+    }
+
+    /// Refuse work after `close()`, or while poisoned by a pipeline panic
+    fn ensure_open(&self) -> Result<(), DetectorError> {
+        if self.closed {
+            return Err(DetectorError::Closed(format!("{}Detector is closed; create a new one to process frames", self.log_prefix())));
+        }
+        if self.poisoned {
+            return Err(DetectorError::PipelinePanic(format!("{}Detector is poisoned by an earlier pipeline panic; call reset() first", self.log_prefix())));
+        }
+        Ok(())
+    }
+
+    /// `decode_frame` behind the optional LRU cache keyed by the input's content hash.
+    /// Only decoding is memoized; analysis always runs, since it is stateful.
+    fn decode_frame_cached(&mut self, frame_b64: &str) -> Result<DecodedFrame, String> {
+        let capacity = if self.config.low_memory { 0 } else { self.config.decode_cache_size };
+        if capacity == 0 {
+            return self.decode_frame(frame_b64);
+        }
+
+        let mut hasher = std::collections::hash_map::DefaultHasher::new();
+        frame_b64.hash(&mut hasher);
+        let key = hasher.finish();
+
+        if let Some(position) = self.decode_cache.iter().position(|(cached_key, _)| *cached_key == key) {
+            let entry = self.decode_cache.remove(position).unwrap();
+            let decoded = entry.1.clone();
+            self.decode_cache.push_back(entry);
+            self.decode_cache_hits += 1;
+            return Ok(decoded);
+        }
+
+        self.decode_cache_misses += 1;
+        let decoded = self.decode_frame(frame_b64)?;
+        if self.decode_cache.len() >= capacity {
+            self.decode_cache.pop_front();
+        }
+        self.decode_cache.push_back((key, decoded.clone()));
+        Ok(decoded)
+    }
+
+    /// Reject a seed frame that doesn't match the locked frame size
+    fn check_seed_size(&self, decoded: &DecodedFrame, label: &str) -> Result<(), DetectorError> {
+        let (width, height) = Self::rotated_size(decoded, self.config.rotation);
+        match self.source_size {
+            Some((locked_width, locked_height)) if (locked_width, locked_height) != (width, height) => {
+                Err(DetectorError::DimensionMismatch(format!(
+                    "{} is {}x{} but the pipeline was initialized for {}x{}",
+                    label, width, height, locked_width, locked_height
+                )))
+            }
+            _ => Ok(()),
+        }
+    }
+
+    /// Replace the pipeline with a fresh one fed the seed frames until it is calibrated.
+    /// Frames play forward and back (0, 1, .., n-1, n-2, .., 1, 0, ..) so the history never
+    /// sees a jump from the last frame to the first; more than `REFERENCE_SEED_FRAMES`
+    /// frames are each fed once.
+    fn seed_pipeline(&mut self, frames: Vec<DecodedFrame>) -> Result<(), DetectorError> {
+        // Start from an empty history so the seed replaces, not blends into, what was learned
+        self.pipeline = None;
+        self.chroma_pipelines.clear();
+        self.previous_scene_state = None;
+        self.previous_blobs.clear();
+        self.stable_blob_ids.clear();
+        self.disturbance_hold_remaining = 0;
+        self.spike_promoted = false;
+        self.volatile_run = 0;
+        self.unstable_run = 0;
+        self.last_trigger_eligible = false;
+        self.first_motion_alerted = false;
+
+        let mut planes = Vec::with_capacity(frames.len());
+        for decoded in frames {
+            let (prepared, _) = self.prepare_frame(decoded)?;
+            let chroma: Vec<Vec<u8>> = prepared.chroma.iter().flatten().map(|plane| Self::to_pipeline_layout(plane)).collect();
+            planes.push((prepared.luma, chroma));
+        }
+        self.feed_seed_planes(&planes);
+        Ok(())
+    }
+
+    /// Feed prepared seed planes (row-major luma, then any chroma in pipeline layout) to the
+    /// current pipelines until they are calibrated, in the order `seed_pipeline` describes
+    fn feed_seed_planes(&mut self, planes: &[(Vec<u8>, Vec<Vec<u8>>)]) {
+        let count = planes.len();
+        let order: Vec<usize> = (0..count).chain((1..count.saturating_sub(1)).rev()).collect();
+        let feeds = count.max(REFERENCE_SEED_FRAMES as usize);
+        for &index in order.iter().cycle().take(feeds) {
+            let (luma, chroma) = &planes[index];
+            self.pipeline.as_mut().unwrap().process_frame(luma);
+            for (pipeline, plane) in self.chroma_pipelines.iter_mut().zip(chroma) {
+                pipeline.process_frame(plane);
+            }
+        }
+        self.pipeline_frames = feeds as u64;
+    }
+
+    /// Frame size after the configured rotation (quarter turns swap width and height)
+    fn rotated_size(decoded: &DecodedFrame, rotation: Option<Rotation>) -> (u32, u32) {
+        match rotation {
+            Some(Rotation::Cw90 | Rotation::Cw270) => (decoded.height, decoded.width),
+            _ => (decoded.width, decoded.height),
+        }
+    }
+
+    /// Rotate, size-check, ROI-crop and equalize a decoded frame into the planes the pipeline
+    /// analyzes, creating the pipeline on first use. Also returns the thumbnail source.
+    fn prepare_frame(&mut self, decoded: DecodedFrame) -> Result<(DecodedFrame, Option<ThumbnailSource>), DetectorError> {
+        // Mounting rotation comes first, so everything downstream (frame size, ROI, regions,
+        // blobs, thumbnails) works in upright coordinates
+        let mut decoded = match self.config.rotation {
+            Some(rotation) => decoded.oriented(rotation.orientation()),
+            None => decoded,
+        };
+        let (actual_width, actual_height) = (decoded.width, decoded.height);
+
+        // The pipeline's chunk grid is sized once; a different frame would index out of bounds
+        match self.source_size {
+            None => self.source_size = Some((actual_width, actual_height)),
+            Some((width, height)) if (width, height) != (actual_width, actual_height) => {
+                self.handle_size_change((width, height), (actual_width, actual_height))?;
+            }
+            Some(_) => {}
+        }
+        if let Some(requested) = self.pending_roi.take() {
+            self.roi = self.clip_roi(requested, actual_width, actual_height);
+            if self.roi.is_none() {
+                log::warn!("{}ROI {:?} lies outside the {}x{} frame; analyzing the whole frame", self.log_prefix(), requested, actual_width, actual_height);
+            }
+        }
+
+        // Keep the untouched source luma for a trigger thumbnail (encoded only on trigger)
+        let thumbnail_source = self.config.thumbnail_max_dim.map(|max_dim| (decoded.luma.clone(), max_dim));
+
+        if !self.exclusion_polygons.is_empty() && self.exclusion_mask.is_none() {
+            self.exclusion_mask = Some(Self::rasterize_polygons(&self.exclusion_polygons, actual_width, actual_height));
+        }
+
+        // The rest of the `preprocessing` chain. Rotation already ran; the temporal average
+        // runs once the frame is prepared (see `analyze_decoded`).
+        let (mut roi_x, mut roi_y) = (0, 0);
+        let (mut cropped_width, mut cropped_height) = (actual_width, actual_height);
+        for step in self.config.preprocessing_steps() {
+            match step {
+                PreprocessStep::Rotate | PreprocessStep::TemporalAverage => {}
+                // Over the whole source frame (minus exclusion zones), so motion inside the ROI
+                // moves the mean as little as possible. A frame that creates a pipeline sets
+                // the reference every later frame is shifted to.
+                PreprocessStep::ExposureCompensation if self.config.exposure_compensation => {
+                    let mean = Self::masked_mean(&decoded.luma, self.exclusion_mask.as_deref());
+                    if self.pipeline.is_none() {
+                        self.exposure_reference = Some(mean);
+                    }
+                    let shift = (self.exposure_reference.unwrap_or(mean) - mean).round() as i16;
+                    if shift != 0 {
+                        for pixel in &mut decoded.luma {
+                            *pixel = (*pixel as i16 + shift).clamp(0, 255) as u8;
+                        }
+                    }
+                }
+                PreprocessStep::ExposureCompensation => {}
+                // Blank exclusion zones in source coordinates, before the ROI crop
+                PreprocessStep::Mask => {
+                    if let Some(mask) = &self.exclusion_mask {
+                        for (index, _) in mask.iter().enumerate().filter(|(_, &excluded)| excluded) {
+                            decoded.luma[index] = 0;
+                            // Chroma planes are differences, so "no colour" is the neutral midpoint
+                            for plane in decoded.chroma.iter_mut().flatten() {
+                                plane[index] = 128;
+                            }
+                        }
+                    }
+                }
+                // Crop every plane to the ROI so the pipeline only ever sees the analyzed area
+                PreprocessStep::Crop => {
+                    if let Some((x, y, width, height)) = self.roi {
+                        decoded.luma = Self::crop_plane(&decoded.luma, actual_width, (x, y, width, height));
+                        if let Some(chroma) = &mut decoded.chroma {
+                            for plane in chroma.iter_mut() {
+                                *plane = Self::crop_plane(plane, actual_width, (x, y, width, height));
+                            }
+                        }
+                        (decoded.width, decoded.height) = (width, height);
+                        (roi_x, roi_y) = (x, y);
+                        (cropped_width, cropped_height) = (width, height);
+                    }
+                }
+                PreprocessStep::Upscale => {
+                    if let Some(min_dim) = self.config.min_analysis_dim {
+                        self.upscale_frame(&mut decoded, min_dim);
+                    }
+                }
+                PreprocessStep::Subsample => {
+                    if self.config.chunk_subsample > 1 {
+                        self.subsample_chunks(&mut decoded, self.config.chunk_subsample);
+                    }
+                }
+                // Contrast boost for dark/low-contrast feeds (luma only; chroma planes are differences)
+                PreprocessStep::Equalize => match self.config.equalization {
+                    Equalization::Off => {}
+                    Equalization::Global => Self::equalize_global(&mut decoded.luma),
+                    Equalization::Clahe => Self::equalize_clahe(&mut decoded.luma, decoded.width, decoded.height, self.config.clahe_tile_size),
+                },
+            }
+        }
+
+        // Initialize pipeline with analysis dimensions if not done yet (or after an ROI change)
+        if self.pipeline.is_none() {
+            let mut config = self.config_template.clone();
+            config.image_width = decoded.width;
+            config.image_height = decoded.height;
+            if self.config.multi_channel {
+                self.chroma_pipelines = vec![VisionPipeline::new(config.clone()), VisionPipeline::new(config.clone())];
+            }
+            self.pipeline = Some(LumaPipeline::new(&config, self.config.tile_size));
+            self.pipeline_config = Some(config);
+            self.pipeline_frames = 0;
+        }
+        self.geometry = Some(FrameGeometry {
+            source_size: (actual_width, actual_height),
+            offset: (roi_x as f64, roi_y as f64),
+            scale: (cropped_width as f64 / decoded.width as f64, cropped_height as f64 / decoded.height as f64),
+        });
+
+        Ok((decoded, thumbnail_source))
+    }
+
+    /// Shared analysis path, attaching the frame's stage timings when profiling
+    fn run_decoded(&mut self, decoded: DecodedFrame, timestamp_ms: u64) -> Result<FrameAnalysis, DetectorError> {
+        self.begin_stage_profile();
+        let result = self.analyze_decoded(decoded, timestamp_ms, false);
+        // Always end the profile, so a failed frame never leaks stages into the next one
+        let profile = self.stage_profile.take();
+        result.map(|mut analysis| {
+            analysis.stage_timings_ms = profile.map(|mut profile| {
+                profile.stages_ms.insert("total".to_string(), profile.started.elapsed().as_secs_f64() * 1000.0);
+                profile.stages_ms
+            });
+            if self.config.duplicate_timestamp_policy == DuplicateTimestampPolicy::Skip {
+                self.last_analysis = Some(analysis.clone());
+            }
+            analysis
+        })
+    }
+
+    /// Lazy pipeline init, analysis and trigger decision. `state_only` stops once the scene
+    /// state is tracked and returns a placeholder result (`quick_state`).
+    fn analyze_decoded(&mut self, decoded: DecodedFrame, timestamp_ms: u64, state_only: bool) -> Result<FrameAnalysis, DetectorError> {
+        self.apply_schedule(timestamp_ms);
+        // Get current time for cooldown calculation
+        let current_time = self.clock_secs(Some(timestamp_ms));
+
+        let duplicate = self.timing.last_timestamp_ms == Some(timestamp_ms);
+        self.timing.observe(timestamp_ms);
+        if duplicate {
+            match self.config.duplicate_timestamp_policy {
+                DuplicateTimestampPolicy::Process => {}
+                DuplicateTimestampPolicy::Skip => {
+                    if let Some(previous) = &self.last_analysis {
+                        self.timing.skipped_duplicates += 1;
+                        return Ok(Self::replayed_result(previous));
+                    }
+                }
+                DuplicateTimestampPolicy::Error => {
+                    return Err(DetectorError::DuplicateTimestamp(format!(
+                        "{}Frame timestamp {} ms repeats the previous frame's", self.log_prefix(), timestamp_ms)));
+                }
+            }
+        }
+        // Judged on the whole source frame, before ROI, zones or equalization change it
+        let uniform_frame = Self::luma_variance(&decoded.luma) < self.config.uniform_variance_threshold as f64;
+        if uniform_frame && self.config.uniform_frame_policy == UniformFramePolicy::Ignore {
+            self.skipped_frames += 1;
+            return Ok(self.ignored_frame_result(timestamp_ms));
+        }
+        let started = self.stage_start();
+        let (mut decoded, thumbnail_source) = self.prepare_frame(decoded)?;
+        self.record_stage("preprocess", started);
+        // TreatAsStable: the pipeline sees the last good frame again, so neither the blank
+        // frame nor the recovery from it registers as change
+        let treat_as_stable = uniform_frame && self.config.uniform_frame_policy == UniformFramePolicy::TreatAsStable;
+        if treat_as_stable {
+            if let Some(good) = &self.last_good_frame {
+                decoded = good.clone();
+            }
+        } else if self.config.uniform_frame_policy == UniformFramePolicy::TreatAsStable && !self.config.low_memory {
+            self.last_good_frame = Some(decoded.clone());
+        }
+        if let Some(frames) = self.config.temporal_average_frames {
+            let started = self.stage_start();
+            self.apply_temporal_average(&mut decoded.luma, frames);
+            self.record_stage("preprocess", started);
+        }
+
+        // A pipeline that has seen nothing yet learns this frame as its background first, so
+        // the frame is compared against itself
+        if self.config.first_frame_policy == FirstFramePolicy::Reference && self.pipeline_frames == 0 {
+            let chroma = decoded.chroma.iter().flatten().map(|plane| Self::to_pipeline_layout(plane)).collect();
+            self.feed_seed_planes(&[(decoded.luma.clone(), chroma)]);
+        }
+
+        // Process through Waldo Vision's multi-layer pipeline
+        let started = self.stage_start();
+        let (outcome, decoded) = match self.config.watchdog_timeout_ms {
+            None => {
+                let pipeline = self.pipeline.as_mut().unwrap();
+                (Self::run_pipelines(pipeline, &mut self.chroma_pipelines, &decoded), decoded)
+            }
+            Some(timeout_ms) => {
+                let (width, height) = (decoded.width, decoded.height);
+                match self.run_pipelines_watched(decoded, timeout_ms)? {
+                    Some(finished) => finished,
+                    None => {
+                        log::warn!(
+                            "{}Waldo Vision missed the {} ms watchdog on frame {} ({}x{} analyzed, timestamp {} ms); \
+                             abandoning the pipeline, which recalibrates from the next frame",
+                            self.log_prefix(), timeout_ms, self.frame_count + 1, width, height, timestamp_ms
+                        );
+                        self.invalidate_pipeline();
+                        self.frame_count += 1;
+                        let mut result = self.ignored_frame_result(timestamp_ms);
+                        result.uniform_frame = uniform_frame;
+                        result.timed_out = true;
+                        return Ok(result);
+                    }
+                }
+            }
+        };
+        let (mut analysis, chroma_analyses) = match outcome {
+            Ok(analyses) => analyses,
+            Err(payload) => {
+                let message = payload.downcast_ref::<&str>().map(|message| message.to_string())
+                    .or_else(|| payload.downcast_ref::<String>().cloned())
+                    .unwrap_or_else(|| "unknown panic".to_string());
+                self.poisoned = self.config.poison_on_panic;
+                return Err(DetectorError::PipelinePanic(format!(
+                    "{}Waldo Vision panicked on frame {} ({}x{} analyzed){}: {}",
+                    self.log_prefix(), self.frame_count + 1, decoded.width, decoded.height,
+                    if self.poisoned { "; detector poisoned until reset()" } else { "" }, message
+                )));
+            }
+        };
+        let mut channel_states = Vec::new();
+        if let Some(chroma_analyses) = chroma_analyses {
+            let mut analyses = vec![analysis];
+            analyses.extend(chroma_analyses);
+            channel_states = analyses.iter().map(|a| Self::scene_state_name(&a.scene_state).to_string()).collect();
+            analysis = Self::combine_channels(self.config.channel_combine, analyses);
+        }
+        if !self.config.low_memory {
+            self.last_report = Some(analysis.report.clone());
+        }
+        let calibrating = analysis.scene_state == SceneState::Calibrating;
+        // A background model re-classifies chunks on its own, at the pipeline's 2.0 rule unless a threshold is set
+        let chunk_threshold = self.config.chunk_disturbance_threshold.map(f64::from)
+            .or(self.config.background_model.map(|_| PIPELINE_STABLE_DELTA));
+        let needs_deltas = chunk_threshold.is_some() || (self.config.calibration_feedback && calibrating);
+        let chunk_means = (needs_deltas || !self.config.low_memory).then(|| self.chunk_means(&decoded));
+        // Measured whenever the means exist: hotspots need them even without a threshold
+        let chunk_deltas = chunk_means.as_ref().and_then(|means| self.chunk_deltas(means));
+        if let Some(means) = chunk_means.filter(|_| !self.config.low_memory) {
+            if self.background_history.len() == PIPELINE_HISTORY_FRAMES {
+                self.background_history.pop_front();
+            }
+            self.background_history.push_back(means);
+        }
+        if let (Some(threshold), Some(deltas)) = (chunk_threshold, &chunk_deltas) {
+            Self::apply_chunk_threshold(&mut analysis.status_map, deltas, threshold);
+        }
+        self.hotspots = chunk_deltas.as_ref().map_or_else(Vec::new, |deltas| Self::rank_hotspots(&analysis.status_map, deltas));
+        self.record_stage("pipeline", started);
+        self.frame_count += 1;
+        self.pipeline_frames += 1;
+        if let Some(limit) = self.config.error_if_calibrating_after {
+            if analysis.scene_state == SceneState::Calibrating && self.pipeline_frames > limit {
+                return Err(DetectorError::Calibration(format!(
+                    "{}Still CALIBRATING after {} frames (error_if_calibrating_after={})",
+                    self.log_prefix(), self.pipeline_frames, limit
+                )));
+            }
+        }
+        let pipeline_disturbed = analysis.scene_state == SceneState::Disturbed;
+        analysis.scene_state = self.apply_spike_promotion(analysis.scene_state, Self::instability(&analysis.status_map));
+        let spiked = !pipeline_disturbed && analysis.scene_state == SceneState::Disturbed;
+        analysis.scene_state = self.apply_disturbance_hold(analysis.scene_state);
+        let held = !pipeline_disturbed && !spiked && analysis.scene_state == SceneState::Disturbed;
+        if treat_as_stable && analysis.scene_state != SceneState::Calibrating {
+            analysis.scene_state = SceneState::Stable;
+        }
+        if state_only {
+            // Keep the state the next full frame measures its edges and escalation against
+            self.volatile_run = if analysis.scene_state == SceneState::Volatile { self.volatile_run.saturating_add(1) } else { 0 };
+            self.previous_scene_state = Some(analysis.scene_state);
+            self.last_trigger_eligible = false;
+            return Ok(self.ignored_frame_result(timestamp_ms));
+        }
+
+        // Get scene state string
+        let scene_state_str = Self::scene_state_name(&analysis.scene_state);
+
+        let disturbed_fraction = match &chunk_deltas {
+            // The pipeline's chunks are still learning, so measure the change directly
+            Some(deltas) if self.config.calibration_feedback && calibrating => {
+                let threshold = self.config.chunk_disturbance_threshold.map_or(PIPELINE_STABLE_DELTA, |threshold| threshold as f64);
+                let moving = deltas.iter().filter(|&&delta| delta >= threshold).count();
+                if deltas.is_empty() { 0.0 } else { moving as f32 / deltas.len() as f32 }
+            }
+            _ => Self::disturbed_fraction(&analysis.status_map),
+        };
+        let coverage_fraction = Self::coverage_fraction(&analysis);
+        let frozen = self.update_freeze(&analysis.scene_state, disturbed_fraction);
+        let scene_state_scores = self.scene_state_scores(&analysis);
+        
+        // Scene activity edges relative to the previous frame (Stable <-> Volatile/Disturbed)
+        let previous_rank = self.previous_scene_state.as_ref().map(Self::scene_state_rank);
+        let previous_state_name = self.previous_scene_state.as_ref().map(Self::scene_state_name);
+        let rank = Self::scene_state_rank(&analysis.scene_state);
+        // Edges rise from Stable only; leaving calibration straight into activity is no onset
+        let became_active = previous_rank == Some(1) && rank >= 2;
+        let became_calm = previous_rank.is_some_and(|previous| previous >= 2) && rank == 1;
+        self.previous_scene_state = Some(analysis.scene_state.clone());
+        self.volatile_run = if analysis.scene_state == SceneState::Volatile { self.volatile_run.saturating_add(1) } else { 0 };
+
+        // Significant-only filter: frames without a (windowed) new/completed moment never trigger
+        let has_moment = !self.config.require_significant_moment || self.significant_moment_count(&analysis.report) > 0;
+
+        // Same conditions as the decision below, minus the cooldown checks
+        self.last_trigger_eligible = has_moment && if self.config.edge_trigger_mode {
+            became_active
+        } else {
+            match analysis.scene_state {
+                SceneState::Calibrating | SceneState::Stable => false,
+                SceneState::Volatile => self.config.escalate_volatile_after_frames.is_some_and(|frames| self.volatile_run >= frames),
+                SceneState::Disturbed => true,
+            }
+        };
+
+        // Global rate cap: any trigger within the interval of the previous one is held back,
+        // after (and on top of) the per-path cooldowns
+        let globally_blocked = self.config.global_min_trigger_interval_secs
+            .zip(self.last_trigger_time)
+            .is_some_and(|(interval, last)| current_time - last < interval);
+
+        // Arming: the first Disturbed frame after calibration triggers past every gate, once
+        let first_motion = self.config.alert_on_first_motion && !self.first_motion_alerted
+            && analysis.scene_state == SceneState::Disturbed;
+        if first_motion {
+            self.first_motion_alerted = true;
+            self.last_trigger_eligible = true;
+        }
+
+        let (should_trigger, confidence, trigger_reason) = if first_motion {
+            // Later Disturbed frames cool down from this trigger as usual
+            self.last_disturbed_trigger = Some(current_time);
+            self.disturbed_triggers += 1;
+            let reason = format!("First motion after calibration (alert_on_first_motion), fixed confidence {}", FIRST_MOTION_CONFIDENCE);
+            (true, FIRST_MOTION_CONFIDENCE, Some(reason))
+        } else if !has_moment {
+            (false, 0.0, None)
+        } else if self.config.edge_trigger_mode {
+            // Edge mode: one trigger per activity onset, no cooldown needed
+            if became_active && !globally_blocked {
+                self.edge_triggers += 1;
+                let previous = previous_state_name.unwrap_or("NONE");
+                let edge = format!("Edge {} -> {}", previous, scene_state_str);
+                match self.config.edge_trigger_confidence {
+                    Some(confidence) => (true, confidence, Some(format!("{}, fixed edge confidence {}", edge, confidence))),
+                    None => (true, self.disturbed_confidence(&analysis), Some(self.disturbed_reason(&edge, &analysis))),
+                }
+            } else {
+                (false, 0.0, None)
+            }
+        } else {
+            match analysis.scene_state {
+                // Calibrating or Stable: Don't trigger Gemini
+                SceneState::Calibrating => (false, 0.0, None),
+                SceneState::Stable => (false, 0.0, None),
+            
+                // Volatile: IGNORE - only trigger on truly significant DISTURBED events,
+                // unless sustained volatility escalation is enabled (1-second cooldown)
+                SceneState::Volatile => match self.config.escalate_volatile_after_frames {
+                    Some(frames) if self.volatile_run >= frames => {
+                        if cooldown_remaining(self.last_volatile_trigger, VOLATILE_COOLDOWN_SECS, current_time) == 0.0 && !globally_blocked {
+                            self.last_volatile_trigger = Some(current_time);
+                            self.volatile_triggers += 1;
+                            let reason = format!(
+                                "Volatile escalation: {} consecutive Volatile frames (threshold {}), cooldown elapsed, fixed confidence {}",
+                                self.volatile_run, frames, VOLATILE_ESCALATION_CONFIDENCE
+                            );
+                            (true, VOLATILE_ESCALATION_CONFIDENCE, Some(reason))
+                        } else {
+                            (false, 0.0, None) // Still in cooldown
+                        }
+                    }
+                    _ => (false, 0.0, None),
+                },
+            
+                // Disturbed: Trigger with 0.25-second cooldown (new actors/actions)
+                SceneState::Disturbed => {
+                    let disturbed_cooldown = self.disturbed_cooldown(&analysis); // Quarter second - urgent! (less with adaptive_cooldown)
+                    if cooldown_remaining(self.last_disturbed_trigger, disturbed_cooldown, current_time) == 0.0 && !globally_blocked {
+                        self.last_disturbed_trigger = Some(current_time);
+                        self.disturbed_triggers += 1;
+                        let state = if spiked {
+                            "Disturbed (spike promotion)"
+                        } else if held {
+                            "Disturbed (held after exit)"
+                        } else {
+                            "Disturbed"
+                        };
+                        let reason = self.disturbed_reason(&format!("{}, cooldown elapsed", state), &analysis);
+                        (true, self.disturbed_confidence(&analysis), Some(reason))
+                    } else {
+                        (false, 0.0, None) // Still in cooldown
+                    }
+                }
+            }
+        };
+        if should_trigger {
+            self.trigger_count += 1;
+            self.last_trigger_time = Some(current_time);
+        } else if self.last_trigger_eligible {
+            self.suppressed_triggers += 1;
+        }
+        // Output shaping only: the raw value already drove the cooldowns above
+        let (confidence, trigger_reason) = match &self.config.confidence_curve {
+            ConfidenceCurve::Linear {} => (confidence, trigger_reason),
+            curve if should_trigger => {
+                let mapped = curve.apply(confidence);
+                let name = if matches!(curve, ConfidenceCurve::Sigmoid { .. }) { "sigmoid" } else { "stepped" };
+                (mapped, trigger_reason.map(|reason| format!("{}, {} curve -> {:.1}", reason, name, mapped)))
+            }
+            _ => (confidence, trigger_reason),
+        };
+        let smoothed_confidence = self.smooth_confidence(confidence);
+        let mut blobs = self.blob_infos(&analysis.tracked_blobs);
+        if self.config.deterministic {
+            self.stabilize_blob_ids(&mut blobs);
+        }
+        let removed_objects = self.removed_objects(&blobs);
+        let total_blobs = blobs.len();
+        let blobs = self.reported_blobs(blobs);
+        let calmed = if self.config.trigger_on_calm {
+            // Activity that flaps around the exit threshold would otherwise signal every flap
+            let elapsed = cooldown_remaining(self.last_calm, self.config.calm_cooldown_secs, current_time) == 0.0;
+            if became_calm && elapsed {
+                self.last_calm = Some(current_time);
+            }
+            became_calm && elapsed
+        } else {
+            self.config.edge_trigger_mode && became_calm
+        };
+        let (region_triggers, region_fractions) = self.evaluate_regions(&analysis, current_time);
+        let thumbnail_png_b64 = match thumbnail_source {
+            Some((luma, max_dim)) if should_trigger => {
+                let (source_width, source_height) = self.source_size.unwrap_or_default();
+                self.thumbnail_png_b64(luma, source_width, source_height, max_dim)
+            }
+            _ => None,
+        };
+        if should_trigger {
+            self.queue_trigger(timestamp_ms, confidence, trigger_reason.clone());
+        }
+        if let Some(sink) = &mut self.event_sink {
+            if should_trigger {
+                let message = TriggerMessage {
+                    version: EVENT_SCHEMA_VERSION,
+                    event: "trigger",
+                    detector: self.config.name.as_deref(),
+                    frame_count: self.frame_count,
+                    timestamp_ms,
+                    confidence,
+                    scene_state: scene_state_str,
+                    trigger_reason: trigger_reason.as_deref(),
+                    disturbed_fraction,
+                    blob_count: total_blobs,
+                };
+                sink.send(&message, self.config.event_sink_policy, self.config.event_sink_buffer_size);
+            } else {
+                sink.flush();
+            }
+        }
+
+        Ok(FrameAnalysis {
+            should_trigger,
+            confidence,
+            smoothed_confidence,
+            confidence_normalized: confidence / 100.0,
+            tracked_objects: analysis.tracked_blobs.len(),
+            scene_state: scene_state_str.to_string(),
+            previous_scene_state: previous_state_name.map(str::to_string),
+            frame_count: self.frame_count,
+            timestamp_ms,
+            frame_id: None,
+            disturbed_fraction,
+            coverage_fraction,
+            frozen,
+            calmed,
+            region_triggers,
+            region_fractions,
+            blobs,
+            total_blobs,
+            removed_objects,
+            channel_states,
+            scene_state_scores,
+            thumbnail_png_b64,
+            trigger_reason,
+            stage_timings_ms: None,
+            uniform_frame,
+            calibration_progress: self.calibration_progress(&analysis.scene_state),
+            timed_out: false,
+        })
+    }
+
+    /// Replace `luma` with the rounded per-pixel mean of it and up to `frames - 1` previous
+    /// planes; the window restarts when the plane size changes
+    fn apply_temporal_average(&mut self, luma: &mut [u8], frames: usize) {
+        if self.temporal_sums.len() != luma.len() {
+            self.clear_temporal_average();
+            self.temporal_sums = vec![0; luma.len()];
+        }
+        for (sum, &value) in self.temporal_sums.iter_mut().zip(luma.iter()) {
+            *sum += value as u32;
+        }
+        self.temporal_frames.push_back(luma.to_vec());
+        while self.temporal_frames.len() > frames {
+            let oldest = self.temporal_frames.pop_front().unwrap();
+            for (sum, &value) in self.temporal_sums.iter_mut().zip(&oldest) {
+                *sum -= value as u32;
+            }
+        }
+        let count = self.temporal_frames.len() as u32;
+        for (value, &sum) in luma.iter_mut().zip(&self.temporal_sums) {
+            *value = ((sum + count / 2) / count) as u8;
+        }
+    }
+
+    /// Empty the temporal-average window
+    fn clear_temporal_average(&mut self) {
+        self.temporal_frames.clear();
+        self.temporal_sums.clear();
+    }
+
+    /// Population variance of a luma plane (0 for an empty plane)
+    fn luma_variance(luma: &[u8]) -> f64 {
+        if luma.is_empty() {
+            return 0.0;
+        }
+        let (sum, sum_squares) = luma.iter().fold((0u64, 0u64), |(sum, sum_squares), &value| {
+            (sum + value as u64, sum_squares + (value as u64) * (value as u64))
+        });
+        let count = luma.len() as f64;
+        let mean = sum as f64 / count;
+        (sum_squares as f64 / count - mean * mean).max(0.0)
+    }
+
+    /// The previous result again for a skipped duplicate; the one-off signals it carried
+    /// (trigger, calm, region triggers, thumbnail) are not repeated
+    fn replayed_result(previous: &FrameAnalysis) -> FrameAnalysis {
+        FrameAnalysis {
+            should_trigger: false,
+            calmed: false,
+            region_triggers: Vec::new(),
+            thumbnail_png_b64: None,
+            trigger_reason: None,
+            stage_timings_ms: None,
+            frame_id: None,
+            ..previous.clone()
+        }
+    }
+
+    /// Result for a uniform frame skipped under `UniformFramePolicy::Ignore`: nothing was
+    /// analyzed, so it reports the previous scene state with no activity
+    fn ignored_frame_result(&self, timestamp_ms: u64) -> FrameAnalysis {
+        let scene_state = self.previous_scene_state.as_ref().unwrap_or(&SceneState::Calibrating);
+        FrameAnalysis {
+            should_trigger: false,
+            confidence: 0.0,
+            smoothed_confidence: self.confidence_ema.unwrap_or(0.0),
+            confidence_normalized: 0.0,
+            tracked_objects: 0,
+            scene_state: Self::scene_state_name(scene_state).to_string(),
+            previous_scene_state: self.previous_scene_state.as_ref().map(|state| Self::scene_state_name(state).to_string()),
+            frame_count: self.frame_count,
+            timestamp_ms,
+            frame_id: None,
+            disturbed_fraction: 0.0,
+            coverage_fraction: 0.0,
+            frozen: false,
+            calmed: false,
+            region_triggers: Vec::new(),
+            region_fractions: Default::default(),
+            blobs: Vec::new(),
+            total_blobs: 0,
+            removed_objects: Vec::new(),
+            channel_states: Vec::new(),
+            scene_state_scores: Default::default(),
+            thumbnail_png_b64: None,
+            trigger_reason: None,
+            stage_timings_ms: None,
+            uniform_frame: true,
+            calibration_progress: self.calibration_progress(scene_state),
+            timed_out: false,
+        }
+    }
+
+    /// Run the luma pipeline and, in multi-channel mode, the chroma ones on one frame. A panic
+    /// must not unwind into the caller, so it is caught and handed back as the payload.
+    fn run_pipelines(pipeline: &mut LumaPipeline, chroma_pipelines: &mut [VisionPipeline], decoded: &DecodedFrame) -> PipelineOutcome {
+        std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+            let analysis = pipeline.process_frame(&decoded.luma);
+            let chroma_analyses: Option<Vec<PipelineAnalysis>> = decoded.chroma.as_ref().map(|chroma| {
+                chroma_pipelines.iter_mut().zip(chroma)
+                    .map(|(pipeline, plane)| pipeline.process_frame(&Self::to_pipeline_layout(plane)))
+                    .collect()
+            });
+            (analysis, chroma_analyses)
+        }))
+    }
+
+    /// `run_pipelines` on a worker thread, waiting at most `timeout_ms` for it. The
+    /// pipelines and the frame move to the worker and come back with the outcome; on a
+    /// timeout they stay with the worker (None), so the caller must re-create the pipelines.
+    fn run_pipelines_watched(&mut self, decoded: DecodedFrame, timeout_ms: u64) -> Result<Option<(PipelineOutcome, DecodedFrame)>, DetectorError> {
+        let mut pipeline = self.pipeline.take().unwrap();
+        let mut chroma_pipelines = std::mem::take(&mut self.chroma_pipelines);
+        let (sender, receiver) = std::sync::mpsc::channel();
+        let spawned = std::thread::Builder::new().name("fcd-watchdog".to_string()).spawn(move || {
+            let outcome = Self::run_pipelines(&mut pipeline, &mut chroma_pipelines, &decoded);
+            // Fails only once the detector has given up on this frame
+            let _ = sender.send((pipeline, chroma_pipelines, outcome, decoded));
+        });
+        if let Err(e) = spawned {
+            // The pipelines went down with the closure
+            self.invalidate_pipeline();
+            return Err(DetectorError::PipelinePanic(format!("{}Could not start the watchdog worker thread: {}", self.log_prefix(), e)));
+        }
+        match receiver.recv_timeout(std::time::Duration::from_millis(timeout_ms)) {
+            Ok((pipeline, chroma_pipelines, outcome, decoded)) => {
+                self.pipeline = Some(pipeline);
+                self.chroma_pipelines = chroma_pipelines;
+                Ok(Some((outcome, decoded)))
+            }
+            Err(_) => Ok(None),
+        }
+    }
+
+    /// Share of calibration done: frames seen by the current pipeline over the frames it needs
+    fn calibration_progress(&self, scene_state: &SceneState) -> f32 {
+        if *scene_state == SceneState::Calibrating {
+            (self.pipeline_frames as f32 / CALIBRATION_FRAMES as f32).min(1.0)
+        } else {
+            1.0
+        }
+    }
+
+    /// Fold this frame's confidence into the EMA; seeded with the first frame's value
+    fn smooth_confidence(&mut self, confidence: f32) -> f32 {
+        let Some(alpha) = self.config.confidence_smoothing_alpha else {
+            return confidence;
+        };
+        let smoothed = match self.confidence_ema {
+            Some(previous) => alpha * confidence + (1.0 - alpha) * previous,
+            None => confidence,
+        };
+        self.confidence_ema = Some(smoothed);
+        smoothed
+    }
+
+    /// Grayscale PNG of the source frame, downscaled to fit within `max_dim`, as base64
+    fn thumbnail_png_b64(&self, luma: Vec<u8>, width: u32, height: u32, max_dim: u32) -> Option<String> {
+        let image = image::GrayImage::from_raw(width, height, luma)?;
+        let scale = (max_dim as f64 / width.max(height) as f64).min(1.0); // Never upscale
+        let thumb_width = ((width as f64 * scale).round() as u32).max(1);
+        let thumb_height = ((height as f64 * scale).round() as u32).max(1);
+        let thumbnail = image::imageops::resize(&image, thumb_width, thumb_height, self.config.resize_filter.filter_type());
+        self.png_b64(thumbnail, "Thumbnail")
+    }
+
+    /// Base64 PNG of a grayscale image; `what` names it in the warning if encoding fails
+    fn png_b64(&self, image: image::GrayImage, what: &str) -> Option<String> {
+        use base64::{Engine as _, engine::general_purpose::STANDARD};
+
+        let mut png = std::io::Cursor::new(Vec::new());
+        if let Err(e) = image::DynamicImage::ImageLuma8(image).write_to(&mut png, image::ImageFormat::Png) {
+            log::warn!("{}{} encoding failed: {}", self.log_prefix(), what, e);
+            return None;
+        }
+        Some(STANDARD.encode(png.into_inner()))
+    }
+
+    /// Reported name of a scene state
+    fn scene_state_name(state: &SceneState) -> &'static str {
+        match state {
+            SceneState::Calibrating => "CALIBRATING",
+            SceneState::Stable => "STABLE",
+            SceneState::Volatile => "VOLATILE",
+            SceneState::Disturbed => "DISTURBED",
+        }
+    }
+
+    /// Activity rank of a scene state (Calibrating=0 .. Disturbed=3)
+    fn scene_state_rank(state: &SceneState) -> u8 {
+        match state {
+            SceneState::Calibrating => 0,
+            SceneState::Stable => 1,
+            SceneState::Volatile => 2,
+            SceneState::Disturbed => 3,
+        }
+    }
+
+    /// Pick the channel analysis that represents the frame under the combination rule;
+    /// its report and blobs then drive the trigger decision like a single-channel frame
+    fn combine_channels(rule: ChannelCombine, analyses: Vec<PipelineAnalysis>) -> PipelineAnalysis {
+        let activity = |a: &PipelineAnalysis| (Self::scene_state_rank(&a.scene_state), Self::disturbed_fraction(&a.status_map));
+        let pick = |a: &PipelineAnalysis, b: &PipelineAnalysis| match rule {
+            ChannelCombine::Or => activity(a).partial_cmp(&activity(b)),
+            ChannelCombine::And => activity(b).partial_cmp(&activity(a)),
+            ChannelCombine::Max => activity(a).1.partial_cmp(&activity(b).1),
+        }.unwrap_or(std::cmp::Ordering::Equal);
+        // max_by returns the last maximum; reverse so ties resolve to the earliest (luma) channel
+        analyses.into_iter().rev().max_by(pick).expect("at least the luma channel")
+    }
+
+    /// Convert Waldo Vision's tracked blobs into result objects in `output_coords` space
+    fn blob_infos(&self, tracked_blobs: &[TrackedBlob]) -> Vec<BlobInfo> {
+        let geometry = self.geometry.unwrap_or(FrameGeometry::identity(0, 0));
+        let chunk_w = self.config_template.chunk_width as f64;
+        let chunk_h = self.config_template.chunk_height as f64;
+        let (frame_w, frame_h) = self.pipeline_size().unwrap_or((1, 1));
+        let (small_below, large_from) = self.config.blob_size_thresholds;
+        tracked_blobs.iter().map(|tracked| {
+            let blob = &tracked.latest_blob;
+            let (top_left, bottom_right) = blob.bounding_box;
+            // Coarse size/shape hints from the analysis-pixel bounding box
+            let box_w = (bottom_right.x - top_left.x + 1) as f64 * chunk_w;
+            let box_h = (bottom_right.y - top_left.y + 1) as f64 * chunk_h;
+            let share = (box_w * box_h / (frame_w as f64 * frame_h as f64)) as f32;
+            let size_hint = if share < small_below { "small" } else if share >= large_from { "large" } else { "medium" };
+            let shape_hint = if box_h > box_w * BLOB_SHAPE_ASPECT_RATIO {
+                "tall"
+            } else if box_w > box_h * BLOB_SHAPE_ASPECT_RATIO {
+                "wide"
+            } else {
+                "square"
+            };
+            // Grid coordinates -> analysis pixels; the box covers whole chunks, centers sit mid-chunk
+            let min = geometry.map_point(self.config.output_coords, top_left.x as f64 * chunk_w, top_left.y as f64 * chunk_h);
+            let max = geometry.map_point(self.config.output_coords, (bottom_right.x + 1) as f64 * chunk_w, (bottom_right.y + 1) as f64 * chunk_h);
+            let center = geometry.map_point(
+                self.config.output_coords,
+                (blob.center_of_mass.0 + 0.5) * chunk_w,
+                (blob.center_of_mass.1 + 0.5) * chunk_h,
+            );
+            BlobInfo {
+                id: tracked.id,
+                state: match tracked.state {
+                    TrackedState::New => "NEW",
+                    TrackedState::Tracking => "TRACKING",
+                    TrackedState::Lost => "LOST",
+                    TrackedState::Anomalous => "ANOMALOUS",
+                }.to_string(),
+                bbox: (min.0, min.1, max.0, max.1),
+                center,
+                size_in_chunks: blob.size_in_chunks,
+                age: tracked.age,
+                size_hint: size_hint.to_string(),
+                shape_hint: shape_hint.to_string(),
+            }
+        }).collect()
+    }
+
+    /// Replace tracker ids, whose assignment order within a frame follows hash-map iteration,
+    /// with ids handed out in spatial order; then sort blobs by id
+    fn stabilize_blob_ids(&mut self, blobs: &mut [BlobInfo]) {
+        let mut unseen: Vec<&BlobInfo> = blobs.iter().filter(|blob| !self.stable_blob_ids.contains_key(&blob.id)).collect();
+        unseen.sort_by(|a, b| {
+            let key = |blob: &BlobInfo| [blob.bbox.1, blob.bbox.0, blob.bbox.3, blob.bbox.2, blob.center.1, blob.center.0];
+            key(a).iter().zip(key(b).iter()).map(|(x, y)| x.total_cmp(y)).find(|order| order.is_ne()).unwrap_or(std::cmp::Ordering::Equal)
+        });
+        for blob in unseen {
+            self.stable_blob_ids.insert(blob.id, self.next_stable_blob_id);
+            self.next_stable_blob_id += 1;
+        }
+        // Expired tracks never come back, so their mappings can go
+        self.stable_blob_ids.retain(|tracker_id, _| blobs.iter().any(|blob| blob.id == *tracker_id));
+        for blob in blobs.iter_mut() {
+            blob.id = self.stable_blob_ids[&blob.id];
+        }
+        blobs.sort_by_key(|blob| blob.id);
+    }
+
+    /// Apply `max_reported_blobs`: keep the top blobs by `blob_ranking`, in their original order
+    fn reported_blobs(&self, blobs: Vec<BlobInfo>) -> Vec<BlobInfo> {
+        let Some(limit) = self.config.max_reported_blobs.filter(|&limit| limit < blobs.len()) else {
+            return blobs;
+        };
+        let mut ranked: Vec<usize> = (0..blobs.len()).collect();
+        ranked.sort_by_key(|&index| {
+            let blob = &blobs[index];
+            let key = match self.config.blob_ranking {
+                BlobRanking::Size => (blob.size_in_chunks, blob.age as usize),
+                BlobRanking::Age => (blob.age as usize, blob.size_in_chunks),
+            };
+            (std::cmp::Reverse(key), index)
+        });
+        let kept: std::collections::HashSet<usize> = ranked.into_iter().take(limit).collect();
+        blobs.into_iter().enumerate().filter(|(index, _)| kept.contains(index)).map(|(_, blob)| blob).collect()
+    }
+
+    /// Blobs from the previous frame whose track ended on this one after being established
+    /// (see README "Removed Objects"); remembers `blobs` for the next frame
+    fn removed_objects(&mut self, blobs: &[BlobInfo]) -> Vec<BlobInfo> {
+        let established_age = self.config_template.new_age_threshold;
+        let previous = std::mem::replace(&mut self.previous_blobs, blobs.to_vec());
+        previous.into_iter()
+            // The tracker keeps an unmatched blob as LOST for a few frames, so an occluded
+            // object that reappears in time keeps its id and never gets here
+            .filter(|blob| blob.state == "LOST" && blob.age >= established_age)
+            .filter(|blob| !blobs.iter().any(|current| current.id == blob.id))
+            .map(|blob| BlobInfo { state: "REMOVED".to_string(), ..blob })
+            .collect()
+    }
+
+    /// Disturbed cooldown for this event: the base quarter second, or with `adaptive_cooldown`,
+    /// `base * (1 - confidence / 100 * factor)` clamped to `[adaptive_cooldown_min_ms, base]`
+    fn disturbed_cooldown(&self, analysis: &PipelineAnalysis) -> f64 {
+        if !self.config.adaptive_cooldown {
+            return DISTURBED_COOLDOWN_SECS;
+        }
+        // The raw, pre-curve confidence, so `confidence_curve` never changes which frames trigger
+        let raw_normalized = (self.disturbed_confidence(analysis) / 100.0) as f64;
+        let scaled = DISTURBED_COOLDOWN_SECS * (1.0 - raw_normalized * self.config.adaptive_cooldown_factor as f64);
+        let floor = (self.config.adaptive_cooldown_min_ms as f64 / 1000.0).min(DISTURBED_COOLDOWN_SECS);
+        scaled.clamp(floor, DISTURBED_COOLDOWN_SECS)
+    }
+
+    /// Disturbed trigger confidence: configured base plus the significance and blob coverage
+    /// bonuses, capped at 100
+    fn disturbed_confidence(&self, analysis: &PipelineAnalysis) -> f32 {
+        // Calculate high confidence based on significance
+        let base_confidence = self.config.disturbed_base_confidence;
+        let significance_bonus = self.significance_bonus(&analysis.report);
+        (base_confidence + significance_bonus + self.blob_bonus(analysis)).min(100.0)
+    }
+
+    /// Confidence bonus from blob coverage: `blob_confidence_weight` points at full coverage
+    fn blob_bonus(&self, analysis: &PipelineAnalysis) -> f32 {
+        if self.config.blob_confidence_weight == 0.0 {
+            return 0.0;
+        }
+        self.config.blob_confidence_weight * Self::coverage_fraction(analysis)
+    }
+
+    /// Human-readable trigger explanation: the decision path plus the confidence breakdown
+    fn disturbed_reason(&self, path: &str, analysis: &PipelineAnalysis) -> String {
+        let base = self.config.disturbed_base_confidence;
+        let bonus = self.significance_bonus(&analysis.report);
+        let mut reason = match self.significant_moment_count(&analysis.report) {
+            0 => format!("{}, base confidence {}, no significance bonus", path, base),
+            count => format!(
+                "{}, base confidence {}, +{} significance bonus from {} moment{}",
+                path, base, bonus, count, if count == 1 { "" } else { "s" }
+            ),
+        };
+        let blob_bonus = self.blob_bonus(analysis);
+        if self.config.blob_confidence_weight > 0.0 {
+            reason.push_str(&format!(
+                ", +{:.1} blob coverage bonus ({:.0}% covered)",
+                blob_bonus, Self::coverage_fraction(analysis) * 100.0
+            ));
+        }
+        if base + bonus + blob_bonus > 100.0 {
+            reason.push_str(" (capped at 100)");
+        }
+        reason
+    }
+
+    /// Confidence bonus from significant moments: `new_moment_weight` per counted new moment
+    /// plus `completed_moment_weight` per counted completed one
+    fn significance_bonus(&self, report: &Report) -> f32 {
+        let (new, completed) = self.significant_moment_counts(report);
+        new as f32 * self.config.new_moment_weight + completed as f32 * self.config.completed_moment_weight
+    }
+
+    /// New plus completed significant moments that count toward the bonus
+    fn significant_moment_count(&self, report: &Report) -> usize {
+        let (new, completed) = self.significant_moment_counts(report);
+        new + completed
+    }
+
+    /// (new, completed) significant moments that count toward the bonus (see moment window)
+    fn significant_moment_counts(&self, report: &Report) -> (usize, usize) {
+        let Report::SignificantMention(mention_data) = report else {
+            return (0, 0);
+        };
+        // Moment frame indices come from the pipeline's own clock, not our frame_count
+        let is_recent = |moment: &&Moment| match self.config.significance_moment_window_frames {
+            Some(window) => self.pipeline_frames.saturating_sub(moment.start_frame) <= window,
+            None => true, // Count all moments (default)
+        };
+        (
+            mention_data.new_significant_moments.iter().filter(is_recent).count(),
+            mention_data.completed_significant_moments.iter().filter(is_recent).count(),
+        )
+    }
+
+    /// Fraction of chunks reporting motion (PredictableMotion or AnomalousEvent)
+    fn disturbed_fraction(status_map: &[ChunkStatus]) -> f32 {
+        if status_map.is_empty() {
+            return 0.0;
+        }
+        let moving = status_map.iter()
+            .filter(|s| matches!(s, ChunkStatus::PredictableMotion | ChunkStatus::AnomalousEvent(_)))
+            .count();
+        moving as f32 / status_map.len() as f32
+    }
+
+    /// Per-chunk absolute mean-luma change against the previous analyzed frame, or against the
+    /// `background_model` background (which then takes this frame in), measured on the
+    /// analyzed plane; None on the first frame after a pipeline (re)build
+    fn chunk_deltas(&mut self, means: &[f64]) -> Option<Vec<f64>> {
+        let frames = self.config.background_model_frames as usize;
+        match self.config.background_model {
+            None | Some(BackgroundModel::Adaptive) => {
+                let deltas = (self.previous_chunk_means.len() == means.len())
+                    .then(|| means.iter().zip(&self.previous_chunk_means).map(|(mean, previous)| (mean - previous).abs()).collect());
+                if self.config.background_model.is_some() && deltas.is_some() {
+                    let rate = self.config.background_learning_rate.map_or(1.0 / frames as f64, f64::from);
+                    for (background, mean) in self.previous_chunk_means.iter_mut().zip(means) {
+                        *background += (mean - *background) * rate;
+                    }
+                } else {
+                    self.previous_chunk_means = means.to_vec();
+                }
+                deltas
+            }
+            Some(model) => {
+                if self.background_samples.front().is_some_and(|samples| samples.len() != means.len()) {
+                    self.background_samples.clear();
+                }
+                let deltas = (!self.background_samples.is_empty()).then(|| {
+                    let mut window = Vec::with_capacity(self.background_samples.len());
+                    (0..means.len()).map(|chunk| {
+                        window.clear();
+                        window.extend(self.background_samples.iter().map(|samples| samples[chunk]));
+                        (means[chunk] - Self::window_background(model, &mut window)).abs()
+                    }).collect()
+                });
+                if self.background_samples.len() >= frames {
+                    self.background_samples.pop_front();
+                }
+                self.background_samples.push_back(means.to_vec());
+                deltas
+            }
+        }
+    }
+
+    /// Moving chunks that changed at all, as (grid index, change), largest change first; ties
+    /// keep grid order
+    fn rank_hotspots(status_map: &[ChunkStatus], deltas: &[f64]) -> Vec<(usize, f64)> {
+        let mut hotspots: Vec<(usize, f64)> = status_map.iter().zip(deltas).enumerate()
+            .filter(|(_, (status, &delta))| matches!(status, ChunkStatus::PredictableMotion | ChunkStatus::AnomalousEvent(_)) && delta > 0.0)
+            .map(|(index, (_, &delta))| (index, delta))
+            .collect();
+        hotspots.sort_by(|a, b| b.1.total_cmp(&a.1));
+        hotspots
+    }
+
+    /// One chunk's background over its sample window: the mean, or the median (the mean
+    /// of the middle two for an even count)
+    fn window_background(model: BackgroundModel, window: &mut [f64]) -> f64 {
+        if model != BackgroundModel::Median {
+            return window.iter().sum::<f64>() / window.len() as f64;
+        }
+        window.sort_unstable_by(f64::total_cmp);
+        let middle = window.len() / 2;
+        if window.len().is_multiple_of(2) {
+            (window[middle - 1] + window[middle]) / 2.0
+        } else {
+            window[middle]
+        }
+    }
+
+    /// Mean luma of every whole chunk, row by row (the pixels Waldo Vision averages)
+    fn chunk_means(&self, decoded: &DecodedFrame) -> Vec<f64> {
+        let chunk_w = self.config_template.chunk_width as usize;
+        let chunk_h = self.config_template.chunk_height as usize;
+        let (width, height) = (decoded.width as usize, decoded.height as usize);
+        let (grid_w, grid_h) = (width / chunk_w, height / chunk_h);
+        let mut means = Vec::with_capacity(grid_w * grid_h);
+        for cy in 0..grid_h {
+            for cx in 0..grid_w {
+                let sum: u64 = (cy * chunk_h..(cy + 1) * chunk_h)
+                    .flat_map(|y| &decoded.luma[y * width + cx * chunk_w..y * width + (cx + 1) * chunk_w])
+                    .map(|&value| value as u64)
+                    .sum();
+                means.push(sum as f64 / (chunk_w * chunk_h) as f64);
+            }
+        }
+        means
+    }
+
+    /// Re-classify chunks by their own mean-luma change since the previous frame: moving
+    /// below `threshold` becomes Stable, Stable at or above it becomes PredictableMotion.
+    /// Learning chunks and the first frame of a pipeline are left alone.
+    fn apply_chunk_threshold(status_map: &mut [ChunkStatus], deltas: &[f64], threshold: f64) {
+        if status_map.len() != deltas.len() {
+            return;
+        }
+        for (status, &delta) in status_map.iter_mut().zip(deltas) {
+            let moved = delta >= threshold;
+            match status {
+                ChunkStatus::Learning => {}
+                ChunkStatus::Stable if moved => *status = ChunkStatus::PredictableMotion,
+                ChunkStatus::PredictableMotion | ChunkStatus::AnomalousEvent(_) if !moved => *status = ChunkStatus::Stable,
+                _ => {}
+            }
+        }
+    }
+
+    /// Union of the chunks under active blobs, as a fraction of the analyzed grid
+    fn coverage_fraction(analysis: &PipelineAnalysis) -> f32 {
+        if analysis.status_map.is_empty() {
+            return 0.0;
+        }
+        let covered: std::collections::HashSet<(u32, u32)> = analysis.tracked_blobs.iter()
+            .filter(|tracked| tracked.state != TrackedState::Lost)
+            .flat_map(|tracked| tracked.latest_blob.chunk_coords.iter().map(|point| (point.x, point.y)))
+            .collect();
+        covered.len() as f32 / analysis.status_map.len() as f32
+    }
+
+    /// Share of chunks not Stable (Learning included): the metric the pipeline's state machine uses
+    fn instability(status_map: &[ChunkStatus]) -> f32 {
+        if status_map.is_empty() {
+            return 0.0;
+        }
+        let unstable = status_map.iter().filter(|s| !matches!(s, ChunkStatus::Stable)).count();
+        unstable as f32 / status_map.len() as f32
+    }
+
+    /// Soft 0-1 score per scene state, from the same instability metric (share of chunks not
+    /// Stable) and thresholds the pipeline's state machine uses; see README "Scene State Scores"
+    fn scene_state_scores(&mut self, analysis: &PipelineAnalysis) -> std::collections::BTreeMap<String, f32> {
+        let mut scores = std::collections::BTreeMap::new();
+        if analysis.scene_state == SceneState::Calibrating {
+            self.unstable_run = 0;
+            for (state, score) in [("CALIBRATING", 1.0), ("STABLE", 0.0), ("VOLATILE", 0.0), ("DISTURBED", 0.0)] {
+                scores.insert(state.to_string(), score);
+            }
+            return scores;
+        }
+
+        let entry = self.config_template.disturbance_entry_threshold as f32;
+        let exit = self.config_template.disturbance_exit_threshold as f32;
+        let instability = Self::instability(&analysis.status_map);
+        self.unstable_run = if instability > entry { self.unstable_run.saturating_add(1) } else { 0 };
+
+        // Activity ramps from the exit to the entry threshold; confirmation ramps over the
+        // frames the pipeline needs above entry before it escalates to Disturbed
+        let activity = ((instability - exit) / (entry - exit).max(f32::EPSILON)).clamp(0.0, 1.0);
+        let confirmation = (self.unstable_run as f32 / self.config_template.disturbance_confirmation_frames.max(1) as f32).min(1.0);
+        scores.insert("CALIBRATING".to_string(), 0.0);
+        scores.insert("STABLE".to_string(), 1.0 - activity);
+        scores.insert("VOLATILE".to_string(), activity * (1.0 - confirmation));
+        scores.insert("DISTURBED".to_string(), activity * confirmation);
+        scores
+    }
+
+    /// Per-region motion fraction and triggers; each region has its own cooldown timer
+    fn evaluate_regions(&mut self, analysis: &PipelineAnalysis, current_time: f64) -> (Vec<String>, std::collections::BTreeMap<String, f32>) {
+        let mut triggers = Vec::new();
+        let mut fractions = std::collections::BTreeMap::new();
+        let Some(geometry) = self.geometry else {
+            return (triggers, fractions);
+        };
+        let chunk_w = self.config_template.chunk_width;
+        let chunk_h = self.config_template.chunk_height;
+        let grid_w = self.pipeline_size().map_or(0, |(width, _)| width / chunk_w);
+        let grid_h = self.pipeline_size().map_or(0, |(_, height)| height / chunk_h);
+        let entry_threshold = self.config_template.disturbance_entry_threshold as f32;
+        let calibrating = analysis.scene_state == SceneState::Calibrating;
+
+        for region in &mut self.regions {
+            let (x, y, width, height) = region.rect;
+            let (x0, y0) = geometry.source_to_analysis(x as f64, y as f64);
+            let (x1, y1) = geometry.source_to_analysis((x + width) as f64, (y + height) as f64);
+            // Chunks whose area overlaps the region
+            let cx0 = (x0.max(0.0) / chunk_w as f64).floor() as u32;
+            let cy0 = (y0.max(0.0) / chunk_h as f64).floor() as u32;
+            let cx1 = ((x1 / chunk_w as f64).ceil().max(0.0) as u32).min(grid_w);
+            let cy1 = ((y1 / chunk_h as f64).ceil().max(0.0) as u32).min(grid_h);
+            let region_statuses: Vec<&ChunkStatus> = (cy0..cy1)
+                .flat_map(|cy| (cx0..cx1).map(move |cx| (cy * grid_w + cx) as usize))
+                .filter_map(|index| analysis.status_map.get(index))
+                .collect();
+            let moving = region_statuses.iter()
+                .filter(|s| matches!(s, ChunkStatus::PredictableMotion | ChunkStatus::AnomalousEvent(_)))
+                .count();
+            let fraction = if region_statuses.is_empty() { 0.0 } else { moving as f32 / region_statuses.len() as f32 };
+            fractions.insert(region.name.clone(), fraction);
+
+            if !calibrating && fraction > entry_threshold && cooldown_remaining(region.last_trigger, REGION_COOLDOWN_SECS, current_time) == 0.0 {
+                region.last_trigger = Some(current_time);
+                triggers.push(region.name.clone());
+            }
+        }
+        (triggers, fractions)
+    }
+
+    /// Keep the scene Disturbed for `disturbance_hold_frames` frames after the pipeline leaves it
+    fn apply_disturbance_hold(&mut self, scene_state: SceneState) -> SceneState {
+        match scene_state {
+            SceneState::Disturbed => {
+                self.disturbance_hold_remaining = self.config.disturbance_hold_frames;
+                scene_state
+            }
+            // A recalibrating pipeline has dropped its history, so there is nothing to hold
+            SceneState::Calibrating => {
+                self.disturbance_hold_remaining = 0;
+                scene_state
+            }
+            _ if self.disturbance_hold_remaining > 0 => {
+                self.disturbance_hold_remaining -= 1;
+                SceneState::Disturbed
+            }
+            _ => scene_state,
+        }
+    }
+
+    /// Report a Volatile scene as Disturbed once a single frame's instability exceeds
+    /// `disturbance_spike_threshold`, until the pipeline leaves Volatile on its own
+    fn apply_spike_promotion(&mut self, scene_state: SceneState, instability: f32) -> SceneState {
+        match (scene_state, self.config.disturbance_spike_threshold) {
+            (SceneState::Volatile, Some(spike)) => {
+                self.spike_promoted |= instability > spike;
+                if self.spike_promoted { SceneState::Disturbed } else { SceneState::Volatile }
+            }
+            (scene_state, _) => {
+                self.spike_promoted = false;
+                scene_state
+            }
+        }
+    }
+
+    /// Track consecutive motionless frames and report whether the feed looks frozen
+    fn update_freeze(&mut self, scene_state: &SceneState, disturbed_fraction: f32) -> bool {
+        let Some(freeze_frames) = self.config.freeze_frames else {
+            return false;
+        };
+        // Chunks are still learning during calibration, so the fraction means nothing yet
+        if *scene_state == SceneState::Calibrating || disturbed_fraction > self.config.freeze_threshold {
+            self.motionless_run = 0;
+            return false;
+        }
+        self.motionless_run = self.motionless_run.saturating_add(1);
+        self.motionless_run >= freeze_frames
+    }
+
+    /// Clip a requested ROI (x and y may be negative) to the frame; None if nothing remains.
+    /// Logs a warning when the rectangle had to be adjusted.
+    fn clip_roi(&self, requested: (i64, i64, u32, u32), frame_width: u32, frame_height: u32) -> Option<(u32, u32, u32, u32)> {
+        let (x, y, width, height) = requested;
+        let x0 = x.clamp(0, frame_width as i64);
+        let y0 = y.clamp(0, frame_height as i64);
+        let x1 = (x + width as i64).clamp(0, frame_width as i64);
+        let y1 = (y + height as i64).clamp(0, frame_height as i64);
+        if x1 <= x0 || y1 <= y0 {
+            return None;
+        }
+        let clipped = (x0 as u32, y0 as u32, (x1 - x0) as u32, (y1 - y0) as u32);
+        if (clipped.0 as i64, clipped.1 as i64, clipped.2, clipped.3) != requested {
+            log::warn!("{}ROI {:?} clipped to {:?} to fit the {}x{} frame", self.log_prefix(), requested, clipped, frame_width, frame_height);
+        }
+        Some(clipped)
+    }
+
+    /// Enlarge every plane so the frame's shorter side is at least `min_dim`, keeping the aspect ratio
+    fn upscale_frame(&self, decoded: &mut DecodedFrame, min_dim: u32) {
+        let (width, height) = (decoded.width, decoded.height);
+        let shorter = width.min(height);
+        if shorter == 0 || shorter >= min_dim {
+            return;
+        }
+        let factor = min_dim as f64 / shorter as f64;
+        let new_width = ((width as f64 * factor).round() as u32).max(min_dim);
+        let new_height = ((height as f64 * factor).round() as u32).max(min_dim);
+        let filter = self.config.resize_filter.filter_type();
+        let resize = |plane: &mut Vec<u8>| {
+            let image = image::GrayImage::from_raw(width, height, std::mem::take(plane)).expect("plane matches frame size");
+            *plane = image::imageops::resize(&image, new_width, new_height, filter).into_raw();
+        };
+        resize(&mut decoded.luma);
+        for plane in decoded.chroma.iter_mut().flatten() {
+            resize(plane);
+        }
+        (decoded.width, decoded.height) = (new_width, new_height);
+    }
+
+    /// Keep only every `factor`-th chunk row and column, packed into a smaller frame of whole
+    /// chunks; pixels past the last whole chunk (which the pipeline never reads) are dropped
+    fn subsample_chunks(&self, decoded: &mut DecodedFrame, factor: u32) {
+        let chunk_w = self.config_template.chunk_width as usize;
+        let chunk_h = self.config_template.chunk_height as usize;
+        let (width, height) = (decoded.width as usize, decoded.height as usize);
+        let kept_cols: Vec<usize> = (0..width / chunk_w).step_by(factor as usize).collect();
+        let kept_rows: Vec<usize> = (0..height / chunk_h).step_by(factor as usize).collect();
+        if kept_cols.is_empty() || kept_rows.is_empty() {
+            return;
+        }
+        let subsample = |plane: &mut Vec<u8>| {
+            let mut packed = Vec::with_capacity(kept_cols.len() * chunk_w * kept_rows.len() * chunk_h);
+            for &row in &kept_rows {
+                for y in row * chunk_h..(row + 1) * chunk_h {
+                    for &col in &kept_cols {
+                        packed.extend_from_slice(&plane[y * width + col * chunk_w..y * width + (col + 1) * chunk_w]);
+                    }
+                }
+            }
+            *plane = packed;
+        };
+        subsample(&mut decoded.luma);
+        for plane in decoded.chroma.iter_mut().flatten() {
+            subsample(plane);
+        }
+        decoded.width = (kept_cols.len() * chunk_w) as u32;
+        decoded.height = (kept_rows.len() * chunk_h) as u32;
+    }
+
+    /// Copy the `rect` (x, y, width, height) out of a row-major single-channel plane
+    fn crop_plane(plane: &[u8], plane_width: u32, rect: (u32, u32, u32, u32)) -> Vec<u8> {
+        let (x, y, width, height) = (rect.0 as usize, rect.1 as usize, rect.2 as usize, rect.3 as usize);
+        let stride = plane_width as usize;
+        let mut cropped = Vec::with_capacity(width * height);
+        for row in y..y + height {
+            cropped.extend_from_slice(&plane[row * stride + x..row * stride + x + width]);
+        }
+        cropped
+    }
+
+    /// Switch to a new effective ROI; the pipeline is rebuilt (and recalibrates) at the next
+    /// frame when the analyzed area actually changes
+    fn apply_roi(&mut self, roi: Option<(u32, u32, u32, u32)>) {
+        if roi != self.roi {
+            self.roi = roi;
+            self.invalidate_pipeline();
+        }
+    }
+
+    /// Apply `frame_size_policy` to a frame of a new size: raise, or rescale the ROI, regions
+    /// and exclusion zones to it and rebuild the pipeline (see README "Frame Size Changes")
+    fn handle_size_change(&mut self, (width, height): (u32, u32), (new_width, new_height): (u32, u32)) -> Result<(), DetectorError> {
+        let aspect = width as f64 / height as f64;
+        let new_aspect = new_width as f64 / new_height as f64;
+        let rescale = ((new_aspect - aspect) / aspect).abs() <= ASPECT_RATIO_TOLERANCE;
+        match self.config.frame_size_policy {
+            FrameSizePolicy::Error => {
+                return Err(DetectorError::DimensionMismatch(format!(
+                    "Frame is {}x{} but the pipeline was initialized for {}x{}",
+                    new_width, new_height, width, height
+                )));
+            }
+            FrameSizePolicy::ReinitOnRescale if !rescale => {
+                return Err(DetectorError::DimensionMismatch(format!(
+                    "Frame is {}x{} (aspect {:.3}) but the pipeline was initialized for {}x{} (aspect {:.3}); \
+                     an aspect-ratio change usually means a different source (FrameSizePolicy.ReinitAlways accepts it)",
+                    new_width, new_height, new_aspect, width, height, aspect
+                )));
+            }
+            _ => {}
+        }
+        if rescale {
+            log::warn!("{}Frame size changed from {}x{} to {}x{}; rebuilding the pipeline", self.log_prefix(), width, height, new_width, new_height);
+        } else {
+            log::warn!(
+                "{}Frame aspect ratio changed from {}x{} to {}x{}; rebuilding the pipeline and stretching the ROI, regions and exclusion zones to fit",
+                self.log_prefix(), width, height, new_width, new_height
+            );
+        }
+        let (sx, sy) = (new_width as f64 / width as f64, new_height as f64 / height as f64);
+        let scale_x = |x: u32| ((x as f64 * sx).round() as u32).min(new_width);
+        let scale_y = |y: u32| ((y as f64 * sy).round() as u32).min(new_height);
+        let scale_rect = |(x, y, w, h): (u32, u32, u32, u32)| {
+            let (x0, y0) = (scale_x(x), scale_y(y));
+            (x0, y0, scale_x(x + w).saturating_sub(x0), scale_y(y + h).saturating_sub(y0))
+        };
+        for region in &mut self.regions {
+            region.rect = scale_rect(region.rect);
+        }
+        for polygon in &mut self.exclusion_polygons {
+            for point in polygon.iter_mut() {
+                *point = (scale_x(point.0), scale_y(point.1));
+            }
+        }
+        self.exclusion_mask = None;
+        // A rectangle that rounds down to nothing falls back to the whole frame
+        self.roi = self.roi.map(scale_rect).filter(|&(_, _, w, h)| w > 0 && h > 0);
+        self.source_size = Some((new_width, new_height));
+        self.invalidate_pipeline();
+        Ok(())
+    }
+
+    /// Drop the pipeline so the next frame rebuilds it (and recalibrates) from scratch
+    fn invalidate_pipeline(&mut self) {
+        self.pipeline = None;
+        self.first_motion_alerted = false;
+        self.chroma_pipelines.clear();
+        self.pipeline_config = None;
+        self.previous_scene_state = None;
+        self.previous_blobs.clear();
+        self.stable_blob_ids.clear();
+        self.last_good_frame = None;
+        self.previous_chunk_means.clear();
+        self.background_samples.clear();
+        self.hotspots.clear();
+        self.last_report = None;
+        self.background_history.clear();
+        self.clear_temporal_average();
+    }
+
+    /// Mean of the plane's pixels outside `mask` (all of them without one); 0 if none remain
+    fn masked_mean(plane: &[u8], mask: Option<&[bool]>) -> f64 {
+        let (sum, count) = match mask {
+            Some(mask) => plane.iter().zip(mask)
+                .filter(|(_, &excluded)| !excluded)
+                .fold((0u64, 0u64), |(sum, count), (&pixel, _)| (sum + pixel as u64, count + 1)),
+            None => (plane.iter().map(|&pixel| pixel as u64).sum(), plane.len() as u64),
+        };
+        if count == 0 { 0.0 } else { sum as f64 / count as f64 }
+    }
+
+    /// Scanline (even-odd) fill of the polygons into a row-major mask; a pixel is excluded
+    /// when its center lies inside any polygon
+    fn rasterize_polygons(polygons: &[Vec<(u32, u32)>], width: u32, height: u32) -> Vec<bool> {
+        let (width, height) = (width as usize, height as usize);
+        let mut mask = vec![false; width * height];
+        let mut crossings = Vec::new();
+        for polygon in polygons {
+            for row in 0..height {
+                let scan_y = row as f64 + 0.5;
+                crossings.clear();
+                for (i, &(x0, y0)) in polygon.iter().enumerate() {
+                    let (x1, y1) = polygon[(i + 1) % polygon.len()];
+                    let (x0, y0, x1, y1) = (x0 as f64, y0 as f64, x1 as f64, y1 as f64);
+                    // Half-open span so a vertex on the scanline is counted once
+                    if (y0 <= scan_y) != (y1 <= scan_y) {
+                        crossings.push(x0 + (scan_y - y0) * (x1 - x0) / (y1 - y0));
+                    }
+                }
+                crossings.sort_by(|a, b| a.total_cmp(b));
+                for span in crossings.chunks_exact(2) {
+                    // Pixels whose center x + 0.5 falls in [start, end)
+                    let start = (span[0] - 0.5).ceil().max(0.0) as usize;
+                    let end = ((span[1] - 0.5).ceil().max(0.0) as usize).min(width);
+                    for cell in &mut mask[row * width + start.min(end)..row * width + end] {
+                        *cell = true;
+                    }
+                }
+            }
+        }
+        mask
+    }
+
+    /// Lookup table spreading a histogram's CDF over 0-255 (identity for single-level input)
+    fn equalization_lut(hist: &[u32; 256], total: u32) -> [u8; 256] {
+        let cdf_min = hist.iter().copied().find(|&count| count > 0).unwrap_or(0);
+        let mut lut = [0u8; 256];
+        let mut cdf = 0u32;
+        for (level, &count) in hist.iter().enumerate() {
+            cdf += count;
+            lut[level] = if total <= cdf_min {
+                level as u8
+            } else {
+                ((cdf.saturating_sub(cdf_min)) as f64 * 255.0 / (total - cdf_min) as f64).round() as u8
+            };
+        }
+        lut
+    }
+
+    /// Global histogram equalization over the whole frame
+    fn equalize_global(luma: &mut [u8]) {
+        let mut hist = [0u32; 256];
+        for &value in luma.iter() {
+            hist[value as usize] += 1;
+        }
+        let lut = Self::equalization_lut(&hist, luma.len() as u32);
+        for value in luma.iter_mut() {
+            *value = lut[*value as usize];
+        }
+    }
+
+    /// Contrast-limited adaptive histogram equalization: per-tile clipped histograms,
+    /// bilinearly blended between tile centers to avoid visible tile seams
+    fn equalize_clahe(luma: &mut [u8], width: u32, height: u32, tile_size: u32) {
+        let (width, height, tile) = (width as usize, height as usize, tile_size.max(1) as usize);
+        let (tiles_x, tiles_y) = (width.div_ceil(tile), height.div_ceil(tile));
+
+        let mut luts = Vec::with_capacity(tiles_x * tiles_y);
+        for tile_y in 0..tiles_y {
+            for tile_x in 0..tiles_x {
+                let (x0, y0) = (tile_x * tile, tile_y * tile);
+                let (x1, y1) = ((x0 + tile).min(width), (y0 + tile).min(height));
+                let mut hist = [0u32; 256];
+                for y in y0..y1 {
+                    for &value in &luma[y * width + x0..y * width + x1] {
+                        hist[value as usize] += 1;
+                    }
+                }
+                let total = ((x1 - x0) * (y1 - y0)) as u32;
+
+                // Clip tall bins and spread the excess evenly so noise isn't over-amplified
+                let limit = ((CLAHE_CLIP_LIMIT * total as f32 / 256.0).ceil() as u32).max(1);
+                let mut excess = 0;
+                for count in hist.iter_mut() {
+                    if *count > limit {
+                        excess += *count - limit;
+                        *count = limit;
+                    }
+                }
+                for (level, count) in hist.iter_mut().enumerate() {
+                    *count += excess / 256 + u32::from((level as u32) < excess % 256);
+                }
+                luts.push(Self::equalization_lut(&hist, total));
+            }
+        }
+
+        // Position of a pixel between the two nearest tile centers along one axis
+        let neighbours = |position: usize, tiles: usize| {
+            let scaled = (position as f32 + 0.5) / tile as f32 - 0.5;
+            let first = (scaled.floor().max(0.0) as usize).min(tiles - 1);
+            let second = (first + 1).min(tiles - 1);
+            (first, second, (scaled - first as f32).clamp(0.0, 1.0))
+        };
+        let columns: Vec<_> = (0..width).map(|x| neighbours(x, tiles_x)).collect();
+        for y in 0..height {
+            let (ty0, ty1, wy) = neighbours(y, tiles_y);
+            for (x, &(tx0, tx1, wx)) in columns.iter().enumerate() {
+                let value = &mut luma[y * width + x];
+                let level = *value as usize;
+                let top = luts[ty0 * tiles_x + tx0][level] as f32 * (1.0 - wx) + luts[ty0 * tiles_x + tx1][level] as f32 * wx;
+                let bottom = luts[ty1 * tiles_x + tx0][level] as f32 * (1.0 - wx) + luts[ty1 * tiles_x + tx1][level] as f32 * wx;
+                *value = (top * (1.0 - wy) + bottom * wy).round() as u8;
+            }
+        }
+    }
+
+    /// Expand a grayscale buffer into the RGBA layout Waldo Vision's grid reads (4 bytes per pixel)
+    fn to_pipeline_layout(gray: &[u8]) -> Vec<u8> {
+        gray.iter().flat_map(|&luma| [luma, luma, luma, u8::MAX]).collect()
+    }
+
+    /// Decode base64 JPEG to raw grayscale buffer for Waldo Vision
+    fn decode_frame(&mut self, frame_b64: &str) -> Result<DecodedFrame, String> {
+        use base64::{Engine as _, engine::general_purpose::STANDARD};
+        
+        // Transports often add surrounding newlines; MIME-style encoders also wrap lines
+        let trimmed = frame_b64.trim();
+        if trimmed.is_empty() {
+            return Err("Frame was empty (no base64 data received)".to_string());
+        }
+        let unwrapped: std::borrow::Cow<str> = if trimmed.contains(|c: char| c.is_ascii_whitespace()) {
+            trimmed.chars().filter(|c| !c.is_ascii_whitespace()).collect::<String>().into()
+        } else {
+            trimmed.into()
+        };
+
+        // Decode base64 using new API
+        let started = self.stage_start();
+        let img_data = STANDARD.decode(unwrapped.as_bytes())
+            .map_err(|e| format!("Base64 decode error: {}", e))?;
+        self.record_stage("base64_decode", started);
+        self.decode_image_bytes(&img_data)
+    }
+
+    /// Decode encoded image bytes (JPEG, PNG, ...) to luma, plus chroma in multi-channel mode
+    fn decode_image_bytes(&mut self, img_data: &[u8]) -> Result<DecodedFrame, String> {
+        let started = self.stage_start();
+        if let Some(max_dim) = self.config.max_frame_dimension {
+            Self::check_frame_dimensions(img_data, max_dim)?;
+        }
+
+        // Four-channel JPEGs (CMYK/YCCK) need an explicit color conversion; unsupported
+        // layouts fail here with their color space named instead of decoding to garbage
+        let (jpeg_color_space, orientation) = if img_data.starts_with(&[0xFF, 0xD8]) {
+            let (color_space, orientation) = Self::jpeg_headers(img_data)?;
+            (Some(color_space), orientation)
+        } else {
+            (None, Orientation::NoTransforms)
+        };
+
+        // A transfer cut short leaves a JPEG without its end-of-image marker; the decoder
+        // would fill the missing rows silently, so it is counted and subject to the policy
+        let truncated = jpeg_color_space.is_some() && Self::jpeg_truncated(img_data);
+        if truncated {
+            self.truncated_frames += 1;
+            if self.config.truncated_jpeg_policy == TruncatedJpegPolicy::Reject {
+                return Err(format!(
+                    "JPEG is truncated ({} bytes, no end-of-image marker after the last scan)", img_data.len()));
+            }
+        }
+
+        // libjpeg-turbo decodes JPEGs directly to a luma plane, skipping the RGB intermediate.
+        // Multi-channel mode needs color, and non-JPEG or four-channel input goes through the
+        // image crate.
+        #[cfg(feature = "turbojpeg")]
+        if self.decoder_backend == DecoderBackend::TurboJpeg && !self.config.multi_channel && !truncated
+            && matches!(jpeg_color_space, Some(ZuneColorSpace::Luma | ZuneColorSpace::YCbCr | ZuneColorSpace::RGB)) {
+            let gray = turbojpeg::decompress(img_data, turbojpeg::PixelFormat::GRAY)
+                .map_err(|e| format!("TurboJPEG decode error: {}", e))?;
+            self.record_stage("image_load", started);
+            // libjpeg-turbo converts color to luma internally; the counters still describe the source
+            if jpeg_color_space == Some(ZuneColorSpace::Luma) {
+                self.grayscale_fast_path_frames += 1;
+            } else {
+                self.color_converted_frames += 1;
+            }
+            self.turbojpeg_frames += 1;
+            return Ok(DecodedFrame { luma: gray.pixels, chroma: None, width: gray.width as u32, height: gray.height as u32 }.oriented(orientation));
+        }
+        
+        // Load image and convert to grayscale for Waldo Vision
+        let img = if jpeg_color_space == Some(ZuneColorSpace::CMYK) {
+            Self::decode_cmyk_jpeg(img_data)?
+        } else {
+            image::load_from_memory(img_data)
+                .map_err(|e| format!("Image load error: {}", e))?
+        };
+        self.record_stage("image_load", started);
+        let started = self.stage_start();
+        let chroma = self.config.multi_channel.then(|| Self::chroma_planes(img.to_rgb8().as_raw()));
+        let gray_img = match img {
+            // Single-channel source (e.g. grayscale industrial cameras): already luma, no conversion
+            image::DynamicImage::ImageLuma8(gray) => {
+                self.grayscale_fast_path_frames += 1;
+                gray
+            }
+            other => {
+                self.color_converted_frames += 1;
+                other.to_luma8()
+            }
+        };
+        let (width, height) = gray_img.dimensions();
+        
+        // Return pixels with actual dimensions, upright per the EXIF orientation tag
+        let decoded = DecodedFrame { luma: gray_img.into_raw(), chroma, width, height }.oriented(orientation);
+        self.record_stage("grayscale", started);
+        Ok(decoded)
+    }
+
+    /// Reject an image whose header declares a side longer than `max_dim`, before the decoder
+    /// allocates its pixels. Headers the image crate can't read are left to the decoder.
+    fn check_frame_dimensions(data: &[u8], max_dim: u32) -> Result<(), String> {
+        let dimensions = image::ImageReader::new(std::io::Cursor::new(data))
+            .with_guessed_format()
+            .ok()
+            .and_then(|reader| reader.into_dimensions().ok());
+        match dimensions {
+            Some((width, height)) if width.max(height) > max_dim => Err(format!(
+                "Frame is {}x{}, larger than max_frame_dimension ({} pixels per side)", width, height, max_dim)),
+            _ => Ok(()),
+        }
+    }
+
+    /// Whether a JPEG ends before the end-of-image marker (FF D9) that closes its last scan.
+    /// Entropy-coded data escapes 0xFF bytes, so the marker can't appear inside a scan; an
+    /// embedded EXIF thumbnail's marker comes before the main image's scan and is skipped.
+    fn jpeg_truncated(data: &[u8]) -> bool {
+        let last_scan = data.windows(2).rposition(|pair| pair == [0xFF, 0xDA]);
+        match last_scan {
+            Some(start) => !data[start..].windows(2).any(|pair| pair == [0xFF, 0xD9]),
+            None => true,
+        }
+    }
+
+    /// Color space and EXIF orientation declared by a JPEG's headers; errors for color
+    /// layouts we cannot convert to luma
+    fn jpeg_headers(data: &[u8]) -> Result<(ZuneColorSpace, Orientation), String> {
+        let mut decoder = zune_jpeg::JpegDecoder::new(zune_core::bytestream::ZCursor::new(data));
+        decoder.decode_headers().map_err(|e| format!("Image load error: {:?}", e))?;
+        let orientation = decoder.exif()
+            .and_then(|exif| Orientation::from_exif_chunk(exif))
+            .unwrap_or(Orientation::NoTransforms);
+        // The header color space is a guess from markers; the frame header's component count
+        // is what the scan data actually holds
+        let components = decoder.info().map_or(0, |info| info.components as usize);
+        match decoder.input_colorspace() {
+            // Adobe transform 2 with only three components: decoders read it as YCbCr
+            Some(ZuneColorSpace::YCCK) if components == 3 => Ok((ZuneColorSpace::YCbCr, orientation)),
+            Some(space @ (ZuneColorSpace::Luma | ZuneColorSpace::YCbCr | ZuneColorSpace::RGB | ZuneColorSpace::CMYK | ZuneColorSpace::YCCK))
+                if space.num_components() == components => Ok((space, orientation)),
+            Some(space) => Err(format!("Unsupported JPEG color space {:?} with {} components", space, components)),
+            None => Err("Unsupported JPEG color space (could not be determined)".to_string()),
+        }
+    }
+
+    /// Decode a CMYK JPEG to RGB. Adobe (APP14) files store inverted ink values, plain
+    /// CMYK files store them directly; both are handled so luma is never inverted.
+    fn decode_cmyk_jpeg(data: &[u8]) -> Result<image::DynamicImage, String> {
+        let options = zune_core::options::DecoderOptions::default().jpeg_set_out_colorspace(ZuneColorSpace::CMYK);
+        let mut decoder = zune_jpeg::JpegDecoder::new_with_options(zune_core::bytestream::ZCursor::new(data), options);
+        let cmyk = decoder.decode().map_err(|e| format!("CMYK JPEG decode error: {:?}", e))?;
+        let (width, height) = decoder.dimensions().ok_or("CMYK JPEG has no dimensions")?;
+
+        let adobe = Self::has_adobe_marker(data);
+        let mut rgb = Vec::with_capacity(cmyk.len() / 4 * 3);
+        for px in cmyk.chunks_exact(4) {
+            // Remaining light per channel (255 = no ink) times remaining light through black
+            let light = |value: u8| if adobe { value as u32 } else { 255 - value as u32 };
+            let k = light(px[3]);
+            rgb.extend(px[..3].iter().map(|&ink| ((light(ink) * k + 127) / 255) as u8));
+        }
+        image::RgbImage::from_raw(width as u32, height as u32, rgb)
+            .map(image::DynamicImage::ImageRgb8)
+            .ok_or_else(|| "CMYK JPEG size does not match its pixel data".to_string())
+    }
+
+    /// Whether a JPEG carries an Adobe APP14 segment (found before the first scan)
+    fn has_adobe_marker(data: &[u8]) -> bool {
+        let mut pos = 2; // Skip SOI
+        while pos + 4 <= data.len() && data[pos] == 0xFF {
+            let marker = data[pos + 1];
+            if marker == 0xDA {
+                break; // Start of scan: no more header segments
+            }
+            if marker == 0xEE && data.get(pos + 4..pos + 9) == Some(b"Adobe".as_slice()) {
+                return true;
+            }
+            pos += 2 + u16::from_be_bytes([data[pos + 2], data[pos + 3]]) as usize;
+        }
+        false
+    }
+
+    /// Weighted luma of interleaved RGB24 pixels
+    fn rgb_to_luma(rgb: &[u8], (r_weight, g_weight, b_weight): (f32, f32, f32)) -> Vec<u8> {
+        rgb.chunks_exact(3)
+            .map(|px| (r_weight * px[0] as f32 + g_weight * px[1] as f32 + b_weight * px[2] as f32).round().clamp(0.0, 255.0) as u8)
+            .collect()
+    }
+
+    /// BT.601 blue- and red-difference planes, offset to 128 so they fit the 8-bit pipeline input
+    fn chroma_planes(rgb: &[u8]) -> [Vec<u8>; 2] {
+        let pixel_count = rgb.len() / 3;
+        let (mut cb, mut cr) = (Vec::with_capacity(pixel_count), Vec::with_capacity(pixel_count));
+        for px in rgb.chunks_exact(3) {
+            let (r, g, b) = (px[0] as f32, px[1] as f32, px[2] as f32);
+            let y = 0.299 * r + 0.587 * g + 0.114 * b;
+            cb.push((128.0 + 0.564 * (b - y)).round().clamp(0.0, 255.0) as u8);
+            cr.push((128.0 + 0.713 * (r - y)).round().clamp(0.0, 255.0) as u8);
+        }
+        [cb, cr]
+    }
+}
+
+/// Input formats this build can analyze: encoded image formats the `image` crate was compiled
+/// to read (for `analyze_frame`, `process_frame` and `process_image_file`), then the raw
+/// pixel layouts (see README "Build Capabilities")
+pub fn supported_input_formats() -> Vec<String> {
+    let mut formats: Vec<String> = image::ImageFormat::all()
+        // `reading_enabled` reports AVIF with the encode-only `avif` feature; decoding it would
+        // need `avif-native`, which is not enabled
+        .filter(|&format| format.reading_enabled() && format != image::ImageFormat::Avif)
+        .map(|format| format!("{:?}", format).to_lowercase())
+        .collect();
+    formats.sort();
+    formats.extend(["gray8".to_string(), "rgb24".to_string()]);
+    formats
+}
+
+/// Optional cargo features compiled into this build
+pub fn supported_features() -> Vec<String> {
+    let mut features = Vec::new();
+    if cfg!(feature = "turbojpeg") {
+        features.push("turbojpeg".to_string());
+    }
+    if cfg!(feature = "dlpack") {
+        features.push("dlpack".to_string());
+    }
+    if cfg!(feature = "arrow") {
+        features.push("arrow".to_string());
+    }
+    features
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const WIDTH: u32 = 160;
+    const HEIGHT: u32 = 120;
+
+    fn detector(config: DetectorConfig) -> CoreDetector {
+        CoreDetector::new(config).expect("valid test config")
+    }
+
+    fn flat_frame(value: u8) -> Vec<u8> {
+        vec![value; (WIDTH * HEIGHT) as usize]
+    }
+
+    /// Pseudo-random WIDTH x HEIGHT frame (xorshift), the same for the same `seed`
+    fn noise_frame(seed: u64) -> Vec<u8> {
+        let mut state = seed.wrapping_mul(0x9E37_79B9_7F4A_7C15) | 1;
+        (0..WIDTH * HEIGHT).map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            (state >> 56) as u8
+        }).collect()
+    }
+
+    /// Base64 binary PGM, the simplest encoded frame `analyze_frame` accepts
+    fn pgm_b64(pixels: &[u8], width: u32, height: u32) -> String {
+        use base64::Engine as _;
+        let mut data = format!("P5\n{} {}\n255\n", width, height).into_bytes();
+        data.extend_from_slice(pixels);
+        base64::engine::general_purpose::STANDARD.encode(data)
+    }
+
+    /// Analyze `frames` at 100 ms intervals from `start_ms`
+    fn run(detector: &mut CoreDetector, frames: impl IntoIterator<Item = Vec<u8>>, start_ms: u64) -> Vec<FrameAnalysis> {
+        frames.into_iter().enumerate()
+            .map(|(index, pixels)| detector.analyze_pixels(pixels, WIDTH, HEIGHT, start_ms + index as u64 * 100).unwrap())
+            .collect()
+    }
+
+    /// A 30x30 bright square moving right over a flat gray background
+    fn moving_square_frame(step: u32) -> Vec<u8> {
+        let mut pixels = flat_frame(80);
+        let left = (step * 4) % (WIDTH - 30);
+        for y in 40..70 {
+            for x in left..left + 30 {
+                pixels[(y * WIDTH + x) as usize] = 250;
+            }
+        }
+        pixels
+    }
+
+    #[test]
+    fn deterministic_replay_gives_identical_results() {
+        let frames = || (0..100).map(|i| if i < 40 { flat_frame(80) } else if i < 70 { moving_square_frame(i) } else { noise_frame(i as u64) });
+        let replay = || {
+            let mut detector = detector(DetectorConfig { deterministic: true, ..Default::default() });
+            run(&mut detector, frames(), 1_000)
+        };
+        let (first, second) = (replay(), replay());
+        assert!(first.iter().any(|analysis| analysis.should_trigger), "the replay should trigger");
+        assert!(first.iter().any(|analysis| !analysis.blobs.is_empty()), "the replay should track blobs");
+        let as_json = |results: &[FrameAnalysis]| results.iter().map(|analysis| serde_json::to_string(analysis).unwrap()).collect::<Vec<_>>();
+        assert_eq!(as_json(&first), as_json(&second));
+    }
+
+    #[test]
+    fn trigger_at_timestamp_zero_starts_cooldown() {
+        let mut detector = detector(DetectorConfig { deterministic: true, ..Default::default() });
+        detector.set_reference_frame(&pgm_b64(&flat_frame(80), WIDTH, HEIGHT)).unwrap();
+        // Motion builds up over a few frames, all stamped 0, until the first trigger
+        let mut index = 0;
+        while !detector.analyze_pixels(noise_frame(index), WIDTH, HEIGHT, 0).unwrap().should_trigger {
+            index += 1;
+            assert!(index < 20, "expected a trigger at timestamp 0");
+        }
+        assert_eq!(detector.cooldown_timers().1, Some(0.0));
+        // Still Disturbed, but within the 0.25 s cooldown of the trigger at 0
+        let next = detector.analyze_pixels(noise_frame(100), WIDTH, HEIGHT, 100).unwrap();
+        assert_eq!(next.scene_state, "DISTURBED");
+        assert!(!next.should_trigger);
+        assert!(cooldown_remaining(Some(0.0), DISTURBED_COOLDOWN_SECS, 0.1) > 0.0);
+        assert_eq!(cooldown_remaining(None, DISTURBED_COOLDOWN_SECS, 0.1), 0.0);
+    }
+
+    /// Mean of `luma` over the columns `columns` of every row
+    fn column_mean(decoded: &DecodedFrame, columns: std::ops::Range<u32>) -> f64 {
+        let rows = decoded.luma.chunks_exact(decoded.width as usize);
+        let values: Vec<u8> = rows.flat_map(|row| row[columns.start as usize..columns.end as usize].to_vec()).collect();
+        values.iter().map(|&v| v as f64).sum::<f64>() / values.len() as f64
+    }
+
+    /// Baseline JPEG of `image`, as the `image` crate encodes it
+    fn jpeg(image: image::DynamicImage) -> Vec<u8> {
+        let mut data = Vec::new();
+        image.write_to(&mut std::io::Cursor::new(&mut data), image::ImageFormat::Jpeg).unwrap();
+        data
+    }
+
+    #[test]
+    fn grayscale_jpegs_take_the_fast_path() {
+        let gray = image::GrayImage::from_fn(WIDTH, HEIGHT, |x, y| image::Luma([((x + y) % 200) as u8 + 20]));
+        let mut detector = detector(DetectorConfig::default());
+        let decoded = detector.decode_image_bytes(&jpeg(image::DynamicImage::ImageLuma8(gray))).unwrap();
+        assert_eq!((decoded.width, decoded.height), (WIDTH, HEIGHT));
+        assert_eq!(detector.decode_stats(), (1, 0));
+
+        let color = image::RgbImage::from_fn(WIDTH, HEIGHT, |x, y| image::Rgb([x as u8, y as u8, 128]));
+        detector.analyze_image_bytes(&jpeg(image::DynamicImage::ImageRgb8(color)), 0).unwrap();
+        assert_eq!(detector.decode_stats(), (1, 1));
+        assert!(detector.metrics_text().contains("frame_change_detector_turbojpeg_frames_total 0"));
+    }
+
+    #[cfg(not(feature = "turbojpeg"))]
+    #[test]
+    fn turbojpeg_backend_falls_back_to_image_without_the_feature() {
+        let mut detector = detector(DetectorConfig { decoder_backend: DecoderBackend::TurboJpeg, ..Default::default() });
+        assert_eq!(detector.decoder_backend(), DecoderBackend::Image);
+        let color = image::RgbImage::from_fn(WIDTH, HEIGHT, |x, y| image::Rgb([x as u8, y as u8, 64]));
+        let analysis = detector.analyze_image_bytes(&jpeg(image::DynamicImage::ImageRgb8(color)), 0).unwrap();
+        assert_eq!(analysis.frame_count, 1);
+        assert_eq!(detector.decode_stats(), (0, 1));
+        assert!(detector.metrics_text().contains("frame_change_detector_turbojpeg_frames_total 0"));
+    }
+
+    #[test]
+    fn cmyk_jpegs_decode_without_inverting_luma() {
+        for fixture in [&include_bytes!("../testdata/cmyk_adobe.jpg")[..], &include_bytes!("../testdata/cmyk_plain.jpg")[..]] {
+            let decoded = detector(DetectorConfig::default()).decode_image_bytes(fixture).unwrap();
+            assert_eq!((decoded.width, decoded.height), (32, 16));
+            // Left half carries no ink, right half full black
+            assert!(column_mean(&decoded, 0..14) > 230.0, "no ink must decode to white");
+            assert!(column_mean(&decoded, 18..32) < 25.0, "full black ink must decode to black");
+        }
+        let analysis = detector(DetectorConfig::default()).analyze_image_bytes(include_bytes!("../testdata/cmyk_adobe.jpg"), 0).unwrap();
+        assert_eq!(analysis.frame_count, 1);
+    }
+
+    #[test]
+    fn unsupported_jpeg_color_space_is_a_decode_error() {
+        let error = detector(DetectorConfig::default())
+            .analyze_image_bytes(include_bytes!("../testdata/two_component.jpg"), 0)
+            .unwrap_err();
+        match error {
+            DetectorError::Decode(message) => assert!(message.contains("Unsupported JPEG color space"), "{}", message),
+            other => panic!("expected DetectorError::Decode, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn empty_and_wrapped_base64_frames() {
+        let mut detector = detector(DetectorConfig::default());
+        for empty in ["", " \r\n\t\n"] {
+            assert_eq!(detector.decode_frame(empty).err().unwrap(), "Frame was empty (no base64 data received)");
+        }
+        let encoded = pgm_b64(&noise_frame(3), WIDTH, HEIGHT);
+        let wrapped: String = encoded.as_bytes().chunks(76).map(|line| std::str::from_utf8(line).unwrap().to_owned() + "\r\n").collect();
+        let decoded = detector.decode_frame(&format!("\n{}", wrapped)).unwrap();
+        assert_eq!((decoded.width, decoded.height), (WIDTH, HEIGHT));
+        assert_eq!(decoded.luma, detector.decode_frame(&encoded).unwrap().luma);
+    }
+
+    /// Lowest and highest value in `luma`
+    fn value_range(luma: &[u8]) -> (u8, u8) {
+        (*luma.iter().min().unwrap(), *luma.iter().max().unwrap())
+    }
+
+    #[test]
+    fn global_equalization_spreads_a_low_contrast_histogram() {
+        // Ten levels around 100, on a horizontal ramp
+        let mut luma: Vec<u8> = (0..WIDTH * HEIGHT).map(|i| 100 + (i % WIDTH * 10 / WIDTH) as u8).collect();
+        CoreDetector::equalize_global(&mut luma);
+        assert_eq!(value_range(&luma), (0, 255));
+        let mut increasing = luma[..WIDTH as usize].to_vec();
+        increasing.dedup();
+        assert_eq!(increasing.len(), 10, "equalization must keep the levels distinct and ordered");
+        assert!(increasing.windows(2).all(|pair| pair[0] < pair[1]));
+
+        let mut flat = flat_frame(90);
+        CoreDetector::equalize_global(&mut flat);
+        assert_eq!(flat, flat_frame(90), "a single-level frame has nothing to spread");
+    }
+
+    #[test]
+    fn clahe_handles_frames_that_are_not_a_multiple_of_the_tile_size() {
+        let (width, height, tile) = (100, 70, 32);
+        let original: Vec<u8> = (0..width * height).map(|i| 110 + ((i % width + i / width) % 20) as u8).collect();
+        let mut luma = original.clone();
+        CoreDetector::equalize_clahe(&mut luma, width, height, tile);
+        assert_eq!(luma.len(), original.len());
+        let (low, high) = value_range(&luma);
+        assert!(high - low > 100, "contrast should widen, got {}..{}", low, high);
+        // The partial bottom-right tile (4x6 pixels) is stretched like the full ones
+        let corner = |plane: &[u8]| -> Vec<u8> {
+            (64..height).flat_map(|y| plane[(y * width + 96) as usize..((y + 1) * width) as usize].to_vec()).collect()
+        };
+        let ((before_low, before_high), (after_low, after_high)) = (value_range(&corner(&original)), value_range(&corner(&luma)));
+        assert!(after_high - after_low > 2 * (before_high - before_low), "{}..{} became {}..{}", before_low, before_high, after_low, after_high);
+    }
+
+    #[test]
+    fn equalization_lifts_faint_motion_in_a_dark_scene() {
+        // A dark scene in 20-27 with a faint square (1 level brighter) moving over it
+        let scene = |step: Option<u32>| {
+            let mut pixels: Vec<u8> = noise_frame(2).iter().map(|v| 20 + v / 32).collect();
+            if let Some(step) = step {
+                let left = (step * 4) % (WIDTH - 30);
+                for y in 40..70 {
+                    for x in left..left + 30 {
+                        pixels[(y * WIDTH + x) as usize] += 1;
+                    }
+                }
+            }
+            pixels
+        };
+        let peak_fraction = |equalization: Equalization| {
+            let mut detector = detector(DetectorConfig { equalization, clahe_tile_size: 32, ..Default::default() });
+            let frames = (0..60).map(|i| scene((i >= 40).then_some(i)));
+            run(&mut detector, frames, 0).iter().skip(40).map(|analysis| analysis.disturbed_fraction).fold(0.0, f32::max)
+        };
+        // Unequalized, a 1-level change stays under the pipeline's 2.0 chunk rule
+        let off = peak_fraction(Equalization::Off);
+        let (global, clahe) = (peak_fraction(Equalization::Global), peak_fraction(Equalization::Clahe));
+        assert!(global > off, "Global {} should beat Off {}", global, off);
+        assert!(clahe > off, "Clahe {} should beat Off {}", clahe, off);
+    }
+
+    #[test]
+    fn roi_is_clipped_to_the_frame() {
+        let detector = detector(DetectorConfig::default());
+        assert_eq!(detector.clip_roi((10, 20, 30, 40), WIDTH, HEIGHT), Some((10, 20, 30, 40)));
+        assert_eq!(detector.clip_roi((0, 0, 1000, 1000), WIDTH, HEIGHT), Some((0, 0, WIDTH, HEIGHT)));
+        assert_eq!(detector.clip_roi((-10, -5, 50, 25), WIDTH, HEIGHT), Some((0, 0, 40, 20)));
+        assert_eq!(detector.clip_roi((150, 100, 50, 50), WIDTH, HEIGHT), Some((150, 100, 10, 20)));
+        assert_eq!(detector.clip_roi((WIDTH as i64, 0, 10, 10), WIDTH, HEIGHT), None);
+        assert_eq!(detector.clip_roi((-50, -50, 50, 50), WIDTH, HEIGHT), None);
+    }
+
+    #[test]
+    fn roi_set_before_the_first_frame_is_applied_on_arrival() {
+        let mut deferred = detector(DetectorConfig::default());
+        deferred.set_roi(-20, 100, 60, 60).unwrap();
+        assert_eq!(deferred.pending_roi(), Some((-20, 100, 60, 60)));
+        assert_eq!(deferred.roi(), None);
+        deferred.analyze_pixels(flat_frame(80), WIDTH, HEIGHT, 0).unwrap();
+        assert_eq!(deferred.pending_roi(), None);
+        assert_eq!(deferred.roi(), Some((0, 100, 40, 20)));
+
+        // Once the size is known, an ROI outside the frame is rejected and the old one kept
+        assert!(matches!(deferred.set_roi(500, 0, 10, 10), Err(DetectorError::Config(_))));
+        assert_eq!(deferred.roi(), Some((0, 100, 40, 20)));
+
+        // A deferred ROI that misses the frame falls back to the whole frame
+        let mut outside = detector(DetectorConfig::default());
+        outside.set_roi(500, 500, 10, 10).unwrap();
+        outside.analyze_pixels(flat_frame(80), WIDTH, HEIGHT, 0).unwrap();
+        assert_eq!((outside.pending_roi(), outside.roi()), (None, None));
+    }
+
+    #[test]
+    fn rotation_turns_every_plane_and_swaps_the_size() {
+        // 3x2, so a mix-up of width and height or of turn direction shows
+        // 1 2 3
+        // 4 5 6
+        let frame = DecodedFrame {
+            luma: vec![1, 2, 3, 4, 5, 6],
+            chroma: Some([vec![10, 20, 30, 40, 50, 60], vec![6, 5, 4, 3, 2, 1]]),
+            width: 3,
+            height: 2,
+        };
+        let cases = [
+            (Rotation::Cw90, (2, 3), vec![4, 1, 5, 2, 6, 3]),
+            (Rotation::Cw180, (3, 2), vec![6, 5, 4, 3, 2, 1]),
+            (Rotation::Cw270, (2, 3), vec![3, 6, 2, 5, 1, 4]),
+        ];
+        for (rotation, size, luma) in cases {
+            assert_eq!(CoreDetector::rotated_size(&frame, Some(rotation)), size, "{:?}", rotation);
+            let turned = frame.clone().oriented(rotation.orientation());
+            assert_eq!((turned.width, turned.height), size, "{:?}", rotation);
+            assert_eq!(turned.luma, luma, "{:?}", rotation);
+            let [cb, cr] = turned.chroma.unwrap();
+            assert_eq!(cb, luma.iter().map(|v| v * 10).collect::<Vec<_>>(), "{:?}", rotation);
+            assert_eq!(cr, luma.iter().map(|v| 7 - v).collect::<Vec<_>>(), "{:?}", rotation);
+        }
+        assert_eq!(CoreDetector::rotated_size(&frame, None), (3, 2));
+    }
+
+    #[test]
+    fn exclusion_polygons_cover_pixels_whose_center_is_inside() {
+        let (width, height) = (48, 40);
+        let at = |mask: &[bool], x: u32, y: u32| mask[(y * width + x) as usize];
+
+        // Right triangle: a pixel is inside when x + y + 1 < 20
+        let triangle = CoreDetector::rasterize_polygons(&[vec![(0, 0), (20, 0), (0, 20)]], width, height);
+        for (x, y) in [(0, 0), (2, 2), (18, 0), (0, 18), (9, 9)] {
+            assert!(at(&triangle, x, y), "({}, {}) should be excluded", x, y);
+        }
+        for (x, y) in [(10, 10), (15, 15), (20, 0), (0, 20), (47, 39)] {
+            assert!(!at(&triangle, x, y), "({}, {}) should be kept", x, y);
+        }
+        assert_eq!(triangle.iter().filter(|&&excluded| excluded).count(), (1..=19).sum::<usize>());
+
+        // Overlapping squares combine as a union, not an even-odd XOR
+        let squares = CoreDetector::rasterize_polygons(
+            &[vec![(10, 10), (30, 10), (30, 30), (10, 30)], vec![(20, 20), (40, 20), (40, 40), (20, 40)]],
+            width,
+            height,
+        );
+        assert!(at(&squares, 25, 25), "the overlap must stay excluded");
+        assert!(at(&squares, 12, 12) && at(&squares, 38, 38));
+        assert!(!at(&squares, 35, 12) && !at(&squares, 12, 35));
+        assert_eq!(squares.iter().filter(|&&excluded| excluded).count(), 400 + 400 - 100);
+    }
+
+    #[test]
+    fn chunk_threshold_reclassifies_by_mean_change() {
+        let mut status_map = vec![ChunkStatus::Stable, ChunkStatus::PredictableMotion, ChunkStatus::Learning, ChunkStatus::Stable];
+        CoreDetector::apply_chunk_threshold(&mut status_map, &[5.0, 0.5, 9.0, 1.0], 1.0);
+        assert!(matches!(status_map[..], [ChunkStatus::PredictableMotion, ChunkStatus::Stable, ChunkStatus::Learning, ChunkStatus::PredictableMotion]));
+
+        // Half the frame brightens by 6 after the scene settles
+        let fraction = |threshold: Option<f32>| {
+            let mut detector = detector(DetectorConfig { chunk_disturbance_threshold: threshold, ..Default::default() });
+            run(&mut detector, (0..40).map(|_| flat_frame(80)), 0);
+            let mut brighter = flat_frame(80);
+            for row in brighter.chunks_exact_mut(WIDTH as usize) {
+                row[..WIDTH as usize / 2].fill(86);
+            }
+            detector.analyze_pixels(brighter, WIDTH, HEIGHT, 4_000).unwrap().disturbed_fraction
+        };
+        assert!((fraction(Some(1.0)) - 0.5).abs() < 0.05, "a low threshold counts the brightened half");
+        assert_eq!(fraction(Some(10.0)), 0.0, "a high threshold ignores a change of 6");
+    }
+
+    #[test]
+    fn tiny_frames_are_upscaled_to_min_analysis_dim() {
+        // A 64x48 frame with an 8x8 square moving across it
+        let tiny = |step: u32| {
+            let mut pixels = vec![60u8; 64 * 48];
+            for y in 20..28 {
+                for x in (step * 3) % 56..(step * 3) % 56 + 8 {
+                    pixels[(y * 64 + x) as usize] = 220;
+                }
+            }
+            pixels
+        };
+        let mut detector = detector(DetectorConfig { min_analysis_dim: Some(240), ..Default::default() });
+        let results: Vec<FrameAnalysis> = (0..60)
+            .map(|step| detector.analyze_pixels(if step < 30 { vec![60; 64 * 48] } else { tiny(step) }, 64, 48, step as u64 * 100).unwrap())
+            .collect();
+        let config = detector.effective_pipeline_config().0;
+        assert_eq!((config.image_width, config.image_height), (320, 240));
+        assert_eq!(detector.geometry.unwrap().scale, (0.2, 0.2));
+        assert!(results.iter().any(|analysis| analysis.disturbed_fraction > 0.0), "the moving square should register on the 32x24 grid");
+    }
+
+    #[test]
+    fn binary_results_round_trip_and_reject_bad_input() {
+        // A triggering frame, so the optional fields (blobs, reason, thumbnail, timings) are filled
+        let mut detector = detector(DetectorConfig { profile: true, thumbnail_max_dim: Some(32), ..Default::default() });
+        let results = run(&mut detector, (0..80).map(|i| if i < 40 { flat_frame(80) } else { noise_frame(i) }), 0);
+        let analysis = results.into_iter().find(|analysis| analysis.should_trigger).expect("noise should trigger");
+        assert!(analysis.thumbnail_png_b64.is_some() && analysis.trigger_reason.is_some());
+
+        let bytes = analysis.to_bytes().unwrap();
+        assert_eq!(bytes[0], FrameAnalysis::WIRE_FORMAT_VERSION);
+        let decoded = FrameAnalysis::from_bytes(&bytes).unwrap();
+        assert_eq!(serde_json::to_string(&decoded).unwrap(), serde_json::to_string(&analysis).unwrap());
+
+        let decode_error = |data: &[u8]| match FrameAnalysis::from_bytes(data) {
+            Err(DetectorError::Decode(message)) => message,
+            other => panic!("expected DetectorError::Decode, got {:?}", other),
+        };
+        assert!(decode_error(&[]).contains("empty"));
+        let mut wrong_version = bytes.clone();
+        wrong_version[0] = FrameAnalysis::WIRE_FORMAT_VERSION.wrapping_add(1);
+        assert!(decode_error(&wrong_version).contains("wire format version"));
+        assert!(decode_error(&bytes[..bytes.len() / 2]).starts_with("Invalid FrameAnalysis bytes"));
+        let mut trailing = bytes.clone();
+        trailing.push(0);
+        assert!(decode_error(&trailing).contains("1 trailing bytes"));
+    }
+
+    #[test]
+    fn same_seed_gives_identical_results() {
+        let replay = |seed: Option<u64>| {
+            let mut detector = detector(DetectorConfig { deterministic: true, seed, ..Default::default() });
+            let frames = (0..80).map(|i| if i < 40 { flat_frame(80) } else { moving_square_frame(i) });
+            run(&mut detector, frames, 0).iter().map(|analysis| serde_json::to_string(analysis).unwrap()).collect::<Vec<_>>()
+        };
+        let first = replay(Some(7));
+        assert_eq!(first, replay(Some(7)));
+        // Nothing reads the seed yet, so a different one must not change anything either
+        assert_eq!(first, replay(Some(8)));
+        assert_eq!(first, replay(None));
+    }
+
+    #[test]
+    fn exposure_compensation_cancels_a_uniform_brightness_ramp() {
+        // A static texture in 60-123 that brightens by 4 per frame after it settles
+        let texture: Vec<u8> = noise_frame(1).iter().map(|v| 60 + v / 4).collect();
+        let states = |exposure_compensation: bool| {
+            let mut detector = detector(DetectorConfig { exposure_compensation, ..Default::default() });
+            let frames = (0..70u8).map(|i| texture.iter().map(|v| v + i.saturating_sub(40) * 4).collect());
+            run(&mut detector, frames, 0).into_iter().skip(40).map(|analysis| analysis.scene_state).collect::<Vec<_>>()
+        };
+        assert!(states(false).iter().any(|state| state == "DISTURBED"), "the ramp alone should disturb the scene");
+        let compensated = states(true);
+        assert!(compensated.iter().all(|state| state == "STABLE"), "{:?}", compensated);
+    }
+
+    #[test]
+    fn first_frame_policy_decides_the_first_result() {
+        for first in [flat_frame(80), noise_frame(5), moving_square_frame(3)] {
+            let mut calibrate = detector(DetectorConfig { first_frame_policy: FirstFramePolicy::Calibrate, ..Default::default() });
+            let analysis = calibrate.analyze_pixels(first.clone(), WIDTH, HEIGHT, 0).unwrap();
+            assert_eq!(analysis.scene_state, "CALIBRATING");
+            assert_eq!((analysis.disturbed_fraction, analysis.should_trigger), (0.0, false));
+            assert_eq!(analysis.calibration_progress, 1.0 / 31.0);
+
+            let mut reference = detector(DetectorConfig { first_frame_policy: FirstFramePolicy::Reference, ..Default::default() });
+            let analysis = reference.analyze_pixels(first.clone(), WIDTH, HEIGHT, 0).unwrap();
+            assert_eq!(analysis.scene_state, "STABLE");
+            assert_eq!((analysis.disturbed_fraction, analysis.should_trigger), (0.0, false));
+            assert_eq!(analysis.calibration_progress, 1.0);
+            let next = reference.analyze_pixels(first.clone(), WIDTH, HEIGHT, 100).unwrap();
+            assert_eq!((next.scene_state.as_str(), next.disturbed_fraction), ("STABLE", 0.0));
+
+            // A rebuilt pipeline starts from its own first frame again
+            reference.recalibrate();
+            let rebuilt = reference.analyze_pixels(noise_frame(9), WIDTH, HEIGHT, 200).unwrap();
+            assert_eq!((rebuilt.scene_state.as_str(), rebuilt.disturbed_fraction), ("STABLE", 0.0));
+        }
+    }
+
+    #[test]
+    fn background_learning_rate_sets_how_fast_a_parked_object_is_absorbed() {
+        // An object appears after calibration and then stays put for 60 frames (6 s)
+        let fractions = |rate: f32| {
+            let mut detector = detector(DetectorConfig {
+                background_model: Some(BackgroundModel::Adaptive),
+                background_learning_rate: Some(rate),
+                ..Default::default()
+            });
+            let frames = (0..100).map(|i| if i < 40 { flat_frame(80) } else { moving_square_frame(5) });
+            run(&mut detector, frames, 0).into_iter().skip(40).map(|analysis| analysis.disturbed_fraction).collect::<Vec<_>>()
+        };
+        let (fast, slow) = (fractions(0.5), fractions(0.002));
+        assert!(fast[0] > 0.0 && slow[0] > 0.0, "both see the object arrive");
+        assert_eq!(fast.last(), Some(&0.0), "a high rate absorbs the object within a few frames");
+        assert!(slow.iter().all(|&fraction| fraction > 0.0), "a low rate keeps flagging it: {:?}", slow);
+    }
+
+    #[test]
+    fn edge_mode_fires_only_on_a_rise_from_stable() {
+        let mut detector = detector(DetectorConfig { edge_trigger_mode: true, deterministic: true, ..Default::default() });
+        let results = run(&mut detector, (0..60).map(|i| if i < 40 { flat_frame(80) } else { noise_frame(i) }), 0);
+        let rises: Vec<&FrameAnalysis> = results.iter().filter(|analysis| analysis.should_trigger).collect();
+        assert_eq!(rises.len(), 1, "one edge for one burst of motion");
+        assert_eq!(rises[0].previous_scene_state.as_deref(), Some("STABLE"));
+
+        // Waldo Vision itself always leaves calibration through Stable; a detector state that
+        // goes from Calibrating straight to Disturbed must not count as an onset either
+        let probe = |detector: &mut CoreDetector, previous: SceneState, timestamp_ms: u64| {
+            detector.previous_scene_state = Some(previous);
+            detector.analyze_pixels(noise_frame(timestamp_ms), WIDTH, HEIGHT, timestamp_ms).unwrap()
+        };
+        let from_calibrating = probe(&mut detector, SceneState::Calibrating, 10_000);
+        assert_eq!(from_calibrating.scene_state, "DISTURBED");
+        assert_eq!(from_calibrating.previous_scene_state.as_deref(), Some("CALIBRATING"));
+        assert!(!from_calibrating.should_trigger);
+        assert!(probe(&mut detector, SceneState::Stable, 20_000).should_trigger, "the same frame after Stable is an edge");
+    }
+
+    #[test]
+    fn confidence_normalized_follows_the_curve() {
+        let triggers = |confidence_curve: ConfidenceCurve| {
+            let mut detector = detector(DetectorConfig { confidence_curve, deterministic: true, ..Default::default() });
+            let results = run(&mut detector, (0..60).map(|i| if i < 40 { flat_frame(80) } else { noise_frame(i) }), 0);
+            results.into_iter().filter(|analysis| analysis.should_trigger).collect::<Vec<_>>()
+        };
+        let linear = triggers(ConfidenceCurve::Linear {});
+        let sigmoid = triggers(ConfidenceCurve::Sigmoid { center: 97.0, steepness: 0.5 });
+        assert_eq!(
+            linear.iter().map(|analysis| analysis.frame_count).collect::<Vec<_>>(),
+            sigmoid.iter().map(|analysis| analysis.frame_count).collect::<Vec<_>>(),
+            "the curve must not change which frames trigger"
+        );
+        for (raw, mapped) in linear.iter().zip(&sigmoid) {
+            assert_eq!(raw.confidence_normalized, raw.confidence / 100.0);
+            assert_eq!(mapped.confidence_normalized, mapped.confidence / 100.0);
+            assert!(mapped.confidence < raw.confidence, "a 95 maps below 50 around a center of 97");
+        }
+    }
+
+    #[test]
+    fn moment_weights_scale_the_confidence_bonus() {
+        let moment = |id: u64| Moment { id, start_frame: 0, end_frame: 0, path: Vec::new(), blob_history: Vec::new(), is_active: false, is_significant: true };
+        let analysis = PipelineAnalysis {
+            report: Report::SignificantMention(MentionData {
+                new_significant_moments: vec![moment(1), moment(2)],
+                completed_significant_moments: vec![moment(3)],
+                is_global_disturbance: false,
+            }),
+            status_map: Vec::new(),
+            tracked_blobs: Vec::new(),
+            scene_state: SceneState::Disturbed,
+            significant_event_count: 3,
+        };
+        // Two new moments and one completed one, over a base low enough not to hit the cap
+        let confidence = |new_moment_weight: f32, completed_moment_weight: f32| {
+            let config = DetectorConfig { disturbed_base_confidence: 40.0, new_moment_weight, completed_moment_weight, ..Default::default() };
+            detector(config).disturbed_confidence(&analysis)
+        };
+        assert_eq!(confidence(5.0, 5.0), 55.0);
+        assert_eq!(confidence(0.0, 0.0), 40.0);
+        assert_eq!(confidence(10.0, 0.0), 60.0);
+        assert_eq!(confidence(0.0, 12.0), 52.0);
+        assert_eq!(confidence(30.0, 30.0), 100.0, "confidence is capped at 100");
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn event_sink_writes_one_line_per_trigger_and_counts_lost_events() {
+        use std::io::BufRead as _;
+
+        for policy in [EventSinkPolicy::Buffer, EventSinkPolicy::Drop] {
+            let dir = std::env::temp_dir().join(format!("frame-change-detector-sink-{}-{:?}", std::process::id(), policy));
+            std::fs::create_dir_all(&dir).unwrap();
+            let path = dir.join("triggers.sock");
+            let _ = std::fs::remove_file(&path);
+            let listener = std::os::unix::net::UnixListener::bind(&path).unwrap();
+
+            let config = DetectorConfig {
+                name: Some("sink-test".into()),
+                deterministic: true, // Cooldowns on frame timestamps, so the 100 ms frames trigger repeatedly
+                event_sink_policy: policy,
+                event_sink_buffer_size: 2,
+                ..Default::default()
+            };
+            let mut detector = detector(config);
+            detector.set_event_sink(Some(&path)).unwrap();
+            let (consumer, _) = listener.accept().unwrap();
+            consumer.set_read_timeout(Some(std::time::Duration::from_secs(5))).unwrap();
+
+            let results = run(&mut detector, (0..70).map(|i| if i < 40 { flat_frame(80) } else { noise_frame(i) }), 0);
+            let triggers: Vec<&FrameAnalysis> = results.iter().filter(|analysis| analysis.should_trigger).collect();
+            assert!(triggers.len() >= 3, "expected several triggers, got {}", triggers.len());
+            let mut lines = std::io::BufReader::new(&consumer).lines();
+            for trigger in &triggers {
+                let message: serde_json::Value = serde_json::from_str(&lines.next().unwrap().unwrap()).unwrap();
+                assert_eq!(message["version"], EVENT_SCHEMA_VERSION);
+                assert_eq!(message["event"], "trigger");
+                assert_eq!(message["detector"], "sink-test");
+                assert_eq!(message["frame_count"], trigger.frame_count);
+                assert_eq!(message["scene_state"], trigger.scene_state.as_str());
+            }
+            let stats = detector.event_sink_stats().unwrap();
+            assert_eq!((stats.connected, stats.sent, stats.buffered, stats.dropped), (true, triggers.len() as u64, 0, 0));
+
+            // The consumer goes away for good; later triggers can't be delivered
+            drop(lines);
+            drop(consumer);
+            drop(listener);
+            std::fs::remove_file(&path).unwrap();
+            let results = run(&mut detector, (0..15).map(|i| noise_frame(100 + i)), 7_000);
+            let lost = results.iter().filter(|analysis| analysis.should_trigger).count() as u64;
+            assert!(lost >= 3, "expected several triggers after the consumer left, got {}", lost);
+            let stats = detector.event_sink_stats().unwrap();
+            assert!(!stats.connected);
+            assert_eq!((stats.sent, stats.reconnects), (triggers.len() as u64, 0));
+            match policy {
+                EventSinkPolicy::Buffer => assert_eq!((stats.buffered, stats.dropped), (2, lost - 2), "the oldest events are evicted"),
+                EventSinkPolicy::Drop => assert_eq!((stats.buffered, stats.dropped), (0, lost)),
+            }
+
+            // The consumer is back within the reconnect backoff: close() still delivers
+            let listener = std::os::unix::net::UnixListener::bind(&path).unwrap();
+            detector.close();
+            assert!(detector.event_sink_stats().is_none());
+            if policy == EventSinkPolicy::Buffer {
+                // close() is synchronous, so its connection is already waiting if it was made
+                listener.set_nonblocking(true).unwrap();
+                let (consumer, _) = listener.accept().expect("close() should reconnect to deliver the buffer");
+                consumer.set_nonblocking(false).unwrap();
+                consumer.set_read_timeout(Some(std::time::Duration::from_secs(5))).unwrap();
+                let delivered: Vec<serde_json::Value> = std::io::BufReader::new(&consumer).lines()
+                    .map(|line| serde_json::from_str(&line.unwrap()).unwrap())
+                    .collect();
+                let last_frames: Vec<u64> = results.iter().filter(|analysis| analysis.should_trigger).map(|analysis| analysis.frame_count).skip(lost as usize - 2).collect();
+                assert_eq!(delivered.iter().map(|message| message["frame_count"].as_u64().unwrap()).collect::<Vec<_>>(), last_frames);
+            }
+            assert!(matches!(detector.set_event_sink(Some(&path)), Err(DetectorError::Closed(_))));
+            drop(listener);
+            std::fs::remove_file(&path).unwrap();
+            std::fs::remove_dir(&dir).unwrap();
+        }
+    }
+}
@@ -0,0 +1,916 @@
+// THEORY:
+// The `python` module is the PyO3 face of the detector. All decision logic lives in
+// `CoreDetector`; this module only converts between Python and Rust types, maps
+// `DetectorError` onto the exception hierarchy, and hosts the conveniences that only
+// make sense with an interpreter around (buffer protocol, iterables, callbacks, the
+// context manager and the legacy tuple-returning methods).
+//
+// Key principles:
+// - Wrappers stay thin: validate Python-only inputs, call the core, convert the result
+// - Every Python name and signature is unchanged from before the split
+// - Compiled only with the `python` cargo feature (on by default)
+
+use pyo3::buffer::PyBuffer;
+use pyo3::create_exception;
+use pyo3::prelude::*;
+use pyo3::types::{PyBytes, PyDict};
+use waldo_vision::core_modules::smart_blob::SmartBlob;
+use waldo_vision::pipeline::{Moment, Report};
+
+use crate::{
+    cooldown_remaining, BackgroundModel, BlobInfo, BlobRanking, ChannelCombine, ConfidenceCurve, CoreDetector,
+    DecoderBackend, DetectorConfig, DetectorError, DuplicateTimestampPolicy, Equalization, EventSinkPolicy, EventSinkStats, FirstFramePolicy,
+    FrameAnalysis, FrameSizePolicy, Hotspot, OutputCoords, PreprocessStep, ResizeFilter, Rotation, SensitivityProfile, SensitivitySchedule,
+    TriggerEvent, TruncatedJpegPolicy, UniformFramePolicy, DISTURBED_COOLDOWN_SECS,
+    VOLATILE_COOLDOWN_SECS,
+};
+
+// Exception hierarchy exported to Python. CorpusError derives from ValueError so callers
+// that caught the old catch-all ValueError keep working.
+create_exception!(frame_change_detector, CorpusError, pyo3::exceptions::PyValueError, "Base class for all frame change detector errors.");
+create_exception!(frame_change_detector, DecodeError, CorpusError, "The frame could not be decoded into an image.");
+create_exception!(frame_change_detector, ConfigError, CorpusError, "The detector configuration is invalid.");
+create_exception!(frame_change_detector, DimensionMismatchError, CorpusError, "The frame does not match the dimensions the pipeline was initialized with.");
+create_exception!(frame_change_detector, CalibrationError, CorpusError, "The pipeline is still calibrating later than `error_if_calibrating_after` allows.");
+create_exception!(frame_change_detector, DetectorClosedError, CorpusError, "The detector was closed and can no longer process frames.");
+create_exception!(frame_change_detector, DuplicateTimestampError, CorpusError, "The frame has the same timestamp as the previous frame.");
+create_exception!(frame_change_detector, SerializeError, CorpusError, "A result or config could not be serialized.");
+create_exception!(frame_change_detector, PipelinePanicError, pyo3::exceptions::PyRuntimeError, "The Waldo Vision pipeline panicked while analyzing a frame.");
+
+impl From<DetectorError> for PyErr {
+    fn from(error: DetectorError) -> Self {
+        let message = error.to_string();
+        match error {
+            DetectorError::Decode(_) => DecodeError::new_err(message),
+            DetectorError::Config(_) => ConfigError::new_err(message),
+            DetectorError::DimensionMismatch(_) => DimensionMismatchError::new_err(message),
+            DetectorError::Calibration(_) => CalibrationError::new_err(message),
+            DetectorError::Closed(_) => DetectorClosedError::new_err(message),
+            DetectorError::DuplicateTimestamp(_) => DuplicateTimestampError::new_err(message),
+            DetectorError::PipelinePanic(_) => PipelinePanicError::new_err(message),
+            DetectorError::Serialize(_) => SerializeError::new_err(message),
+            DetectorError::Io { source, .. } | DetectorError::EventSink { source, .. } => match source.kind() {
+                std::io::ErrorKind::NotFound => pyo3::exceptions::PyFileNotFoundError::new_err(message),
+                std::io::ErrorKind::PermissionDenied => pyo3::exceptions::PyPermissionError::new_err(message),
+                std::io::ErrorKind::ConnectionRefused => pyo3::exceptions::PyConnectionRefusedError::new_err(message),
+                _ => pyo3::exceptions::PyOSError::new_err(message),
+            },
+        }
+    }
+}
+
+#[pymethods]
+impl BlobInfo {
+    fn __repr__(&self) -> String {
+        format!("TrackedBlob(id={}, state={}, bbox={:?})", self.id, self.state, self.bbox)
+    }
+}
+
+#[pymethods]
+impl Hotspot {
+    fn __repr__(&self) -> String {
+        format!("Hotspot(chunk={:?}, center={:?}, disturbance={:.1})", self.chunk, self.center, self.disturbance)
+    }
+}
+
+#[pymethods]
+impl ConfidenceCurve {
+    fn __repr__(&self) -> String {
+        match self {
+            Self::Linear {} => "ConfidenceCurve.Linear()".to_string(),
+            Self::Sigmoid { center, steepness } => format!("ConfidenceCurve.Sigmoid(center={}, steepness={})", center, steepness),
+            Self::Stepped { levels } => format!("ConfidenceCurve.Stepped(levels={:?})", levels),
+        }
+    }
+}
+
+#[pymethods]
+impl SensitivityProfile {
+    #[new]
+    #[pyo3(signature = (name, start, end, change_threshold=None, chunk_disturbance_threshold=None, global_min_trigger_interval_secs=None, calm_cooldown_secs=None))]
+    fn py_new(name: String, start: String, end: String, change_threshold: Option<f32>, chunk_disturbance_threshold: Option<f32>,
+              global_min_trigger_interval_secs: Option<f64>, calm_cooldown_secs: Option<f64>) -> Self {
+        Self { name, start, end, change_threshold, chunk_disturbance_threshold, global_min_trigger_interval_secs, calm_cooldown_secs }
+    }
+
+    fn __repr__(&self) -> String {
+        format!("SensitivityProfile(name={:?}, start={:?}, end={:?})", self.name, self.start, self.end)
+    }
+}
+
+#[pymethods]
+impl SensitivitySchedule {
+    #[new]
+    #[pyo3(signature = (profiles, utc_offset_minutes=0, wall_clock=false))]
+    fn py_new(profiles: Vec<SensitivityProfile>, utc_offset_minutes: i32, wall_clock: bool) -> Self {
+        Self { profiles, utc_offset_minutes, wall_clock }
+    }
+
+    fn __repr__(&self) -> String {
+        let names: Vec<&str> = self.profiles.iter().map(|profile| profile.name.as_str()).collect();
+        format!("SensitivitySchedule(profiles={:?}, utc_offset_minutes={}, wall_clock={})", names, self.utc_offset_minutes, self.wall_clock)
+    }
+}
+
+#[pymethods]
+impl TriggerEvent {
+    fn __repr__(&self) -> String {
+        format!(
+            "TriggerEvent(timestamp_ms={}, confidence={:.1}, coalesced_count={}, span=({}, {}))",
+            self.timestamp_ms, self.confidence, self.coalesced_count, self.first_timestamp_ms, self.last_timestamp_ms
+        )
+    }
+}
+
+#[pymethods]
+impl FrameAnalysis {
+    fn __repr__(&self) -> String {
+        format!(
+            "FrameAnalysis(frame_count={}, scene_state={}, should_trigger={}, confidence={:.1})",
+            self.frame_count, self.scene_state, self.should_trigger, self.confidence
+        )
+    }
+
+    /// Wire format version written by `to_bytes` and required by `from_bytes`
+    #[classattr]
+    #[pyo3(name = "WIRE_FORMAT_VERSION")]
+    fn wire_format_version() -> u8 {
+        Self::WIRE_FORMAT_VERSION
+    }
+
+    /// Compact binary encoding for IPC: one version byte, then the postcard-encoded fields
+    /// (see README "Binary Results")
+    #[pyo3(name = "to_bytes")]
+    fn py_to_bytes<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyBytes>> {
+        Ok(PyBytes::new_bound(py, &self.to_bytes()?))
+    }
+
+    /// Rebuild a result from `to_bytes()` output; raises `DecodeError` on a version mismatch
+    /// or malformed input
+    #[staticmethod]
+    #[pyo3(name = "from_bytes")]
+    fn py_from_bytes(data: &[u8]) -> PyResult<Self> {
+        Ok(Self::from_bytes(data)?)
+    }
+}
+
+/// Waldo Vision-powered frame change detector with intelligent cooldowns (Python wrapper
+/// over `CoreDetector`)
+#[pyclass]
+pub struct FrameChangeDetector {
+    core: CoreDetector,
+}
+
+#[pymethods]
+impl FrameChangeDetector {
+    /// Create a detector. `low_memory` disables optional history buffers and caches
+    /// (see README "Low-Memory Mode"); the core pipeline is unaffected.
+    /// `freeze_frames` enables frozen-feed detection (see README "Freeze Detection").
+    /// `significance_moment_window_frames` limits which moments feed the confidence bonus.
+    /// `output_coords` selects the space for reported coordinates (see README "Output Coordinates").
+    /// `multi_channel` adds chroma sub-pipelines combined via `channel_combine` (see README "Multi-Channel Mode").
+    /// `edge_trigger_mode` triggers only on activity onset and flags `calmed` on return to Stable.
+    /// `decoder_backend` selects the JPEG decoder (see README "Decoder Backends").
+    /// `name` labels this detector in log messages, `repr()` and `get_status()`.
+    /// `strict` raises `ConfigError` for options that are accepted but not honoured yet.
+    #[new]
+    #[pyo3(signature = (_buffer_duration_ms=None, change_threshold=None, _frame_interval_ms=None, low_memory=false, freeze_frames=None, freeze_threshold=0.0, significance_moment_window_frames=None, output_coords=OutputCoords::PixelsAnalysis, multi_channel=false, channel_combine=ChannelCombine::Or, edge_trigger_mode=false, decoder_backend=DecoderBackend::Image, name=None, strict=false))]
+    #[allow(clippy::too_many_arguments)]
+    pub fn new(
+        _buffer_duration_ms: Option<u64>,
+        change_threshold: Option<f32>,
+        _frame_interval_ms: Option<u64>,
+        low_memory: bool,
+        freeze_frames: Option<u32>,
+        freeze_threshold: f32,
+        significance_moment_window_frames: Option<u64>,
+        output_coords: OutputCoords,
+        multi_channel: bool,
+        channel_combine: ChannelCombine,
+        edge_trigger_mode: bool,
+        decoder_backend: DecoderBackend,
+        name: Option<String>,
+        strict: bool,
+    ) -> PyResult<Self> {
+        Self::from_config(DetectorConfig {
+            buffer_duration_ms: _buffer_duration_ms,
+            change_threshold: change_threshold.unwrap_or(5.0),
+            frame_interval_ms: _frame_interval_ms,
+            low_memory,
+            freeze_frames,
+            freeze_threshold,
+            significance_moment_window_frames,
+            output_coords,
+            multi_channel,
+            channel_combine,
+            edge_trigger_mode,
+            decoder_backend,
+            name,
+            strict,
+            // Options added after DetectorConfig are only settable through it
+            ..DetectorConfig::default()
+        })
+    }
+
+    /// Every validation error of `config`, without creating a detector; an empty list means
+    /// it is valid (see README "Config Validation")
+    #[staticmethod]
+    pub fn validate_config(config: &DetectorConfig) -> Vec<String> {
+        CoreDetector::validate_config(config).err().unwrap_or_default()
+    }
+
+    /// Create a detector from a `DetectorConfig` (see README "Configuration Files")
+    #[staticmethod]
+    pub fn from_config(config: DetectorConfig) -> PyResult<Self> {
+        Ok(Self { core: CoreDetector::new(config)? })
+    }
+
+    /// Attach a base64 PNG thumbnail, fit within `max_dim` pixels, to triggering results;
+    /// `None` turns thumbnails off (see README "Trigger Thumbnails")
+    #[pyo3(signature = (max_dim=Some(160)))]
+    pub fn emit_thumbnail_on_trigger(&mut self, max_dim: Option<u32>) -> PyResult<()> {
+        Ok(self.core.emit_thumbnail_on_trigger(max_dim)?)
+    }
+
+    /// Detector label from the config, if any
+    #[getter]
+    pub fn name(&self) -> Option<String> {
+        self.core.name().map(str::to_string)
+    }
+
+    /// Effective JPEG decoder (TurboJpeg falls back to Image if not compiled in)
+    #[getter]
+    pub fn decoder_backend(&self) -> DecoderBackend {
+        self.core.decoder_backend()
+    }
+
+    /// Set by `close()`; frame processing is refused afterwards
+    #[getter]
+    pub fn closed(&self) -> bool {
+        self.core.is_closed()
+    }
+
+    /// A pipeline panic with `poison_on_panic`; frames are refused until `reset()`
+    #[getter]
+    pub fn poisoned(&self) -> bool {
+        self.core.is_poisoned()
+    }
+
+    /// Snapshot of the detector's identity and progress as a dict: name, frame_count, the
+    /// latest scene_state (None before the first frame), frame_size and roi
+    pub fn get_status<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let status = self.core.status();
+        let dict = PyDict::new_bound(py);
+        dict.set_item("name", status.name)?;
+        dict.set_item("frame_count", status.frame_count)?;
+        dict.set_item("scene_state", status.scene_state)?;
+        dict.set_item("frame_size", status.frame_size)?;
+        dict.set_item("roi", status.roi)?;
+        dict.set_item("disturbed_triggers", status.disturbed_triggers)?;
+        dict.set_item("volatile_triggers", status.volatile_triggers)?;
+        dict.set_item("edge_triggers", status.edge_triggers)?;
+        dict.set_item("suppressed_triggers", status.suppressed_triggers)?;
+        dict.set_item("truncated_frames", status.truncated_frames)?;
+        Ok(dict)
+    }
+
+    /// Counters and current scene state in OpenMetrics text format, labelled with the
+    /// detector `name` (see README "Metrics")
+    pub fn metrics_text(&self) -> String {
+        self.core.metrics_text()
+    }
+
+    fn __repr__(&self) -> String {
+        let scene_state = self.core.scene_state().unwrap_or("NONE");
+        match self.core.name() {
+            Some(name) => format!("FrameChangeDetector(name={:?}, frame_count={}, scene_state={})", name, self.core.frame_count(), scene_state),
+            None => format!("FrameChangeDetector(frame_count={}, scene_state={})", self.core.frame_count(), scene_state),
+        }
+    }
+
+    /// Whether optional history buffers and caches are disabled
+    #[getter]
+    pub fn low_memory(&self) -> bool {
+        self.core.config().low_memory
+    }
+
+    /// Copy of the options this detector was created with
+    pub fn get_detector_config(&self) -> DetectorConfig {
+        self.core.config().clone()
+    }
+
+    /// Read an encoded image (JPEG, PNG, ...) from disk and process it like `analyze_frame`,
+    /// without the base64 round trip. See README "Image Files".
+    #[pyo3(signature = (path, timestamp_ms, frame_id=None))]
+    pub fn process_image_file(&mut self, path: std::path::PathBuf, timestamp_ms: u64, frame_id: Option<String>) -> PyResult<FrameAnalysis> {
+        let mut analysis = self.core.analyze_image_file(&path, timestamp_ms)?;
+        analysis.frame_id = frame_id;
+        Ok(analysis)
+    }
+
+    /// Process a raw 8-bit grayscale frame (row-major, one byte per pixel) given as
+    /// `bytes` or a list of ints. Skips decoding entirely; see README "Raw Pixel Input".
+    #[pyo3(signature = (pixels, width, height, timestamp_ms, frame_id=None))]
+    pub fn process_pixels(&mut self, pixels: Vec<u8>, width: u32, height: u32, timestamp_ms: u64, frame_id: Option<String>) -> PyResult<FrameAnalysis> {
+        let mut analysis = self.core.analyze_pixels(pixels, width, height, timestamp_ms)?;
+        analysis.frame_id = frame_id;
+        Ok(analysis)
+    }
+
+    /// Process a raw 8-bit grayscale frame from any buffer-protocol object (memoryview,
+    /// shared memory, numpy array) with one memcpy; see README "Buffer Input".
+    #[pyo3(signature = (buffer, width, height, timestamp_ms, frame_id=None))]
+    pub fn process_buffer(&mut self, py: Python<'_>, buffer: PyBuffer<u8>, width: u32, height: u32, timestamp_ms: u64, frame_id: Option<String>) -> PyResult<FrameAnalysis> {
+        self.core.ensure_open()?;
+        if width == 0 || height == 0 {
+            return Err(DimensionMismatchError::new_err(format!("Frame dimensions must be non-zero, got {}x{}", width, height)));
+        }
+        let expected = width as usize * height as usize;
+        if buffer.item_count() != expected {
+            return Err(DimensionMismatchError::new_err(format!(
+                "Expected {} pixels for a {}x{} frame, got a buffer of {}",
+                expected, width, height, buffer.item_count()
+            )));
+        }
+        if buffer.dimensions() == 2 && buffer.shape() != [height as usize, width as usize] {
+            return Err(DimensionMismatchError::new_err(format!(
+                "Expected a ({}, {}) buffer for a {}x{} frame, got {:?}",
+                height, width, width, height, buffer.shape()
+            )));
+        }
+        if buffer.dimensions() > 2 {
+            return Err(DimensionMismatchError::new_err(format!("Expected a 1-D or 2-D buffer, got shape {:?}", buffer.shape())));
+        }
+        // A straight memcpy when C-contiguous; anything else is gathered into row-major order.
+        // Released right away, so the producer may reuse the memory while analysis runs.
+        let luma = buffer.to_vec(py)?;
+        buffer.release(py);
+        let mut analysis = self.core.analyze_pixels(luma, width, height, timestamp_ms)?;
+        analysis.frame_id = frame_id;
+        Ok(analysis)
+    }
+
+    /// Process an 8-bit grayscale frame from a DLPack tensor or capsule, copied to host
+    /// memory first; needs the `dlpack` feature. See README "DLPack Input".
+    #[cfg(feature = "dlpack")]
+    #[pyo3(signature = (tensor, timestamp_ms, frame_id=None))]
+    pub fn process_dlpack(&mut self, tensor: &Bound<'_, PyAny>, timestamp_ms: u64, frame_id: Option<String>) -> PyResult<FrameAnalysis> {
+        self.core.ensure_open()?;
+        let (luma, width, height) = crate::dlpack::host_luma(tensor)?;
+        let mut analysis = self.core.analyze_pixels(luma, width, height, timestamp_ms)?;
+        analysis.frame_id = frame_id;
+        Ok(analysis)
+    }
+
+    /// Process an interleaved 8-bit RGB frame (`width * height * 3` bytes, any buffer-protocol
+    /// object), converted to luma in Rust with `luma_coefficients`; see README "RGB Input".
+    #[pyo3(signature = (data, width, height, timestamp_ms, frame_id=None))]
+    pub fn process_rgb(&mut self, py: Python<'_>, data: PyBuffer<u8>, width: u32, height: u32, timestamp_ms: u64, frame_id: Option<String>) -> PyResult<FrameAnalysis> {
+        let rgb = data.to_vec(py)?;
+        data.release(py);
+        let mut analysis = self.core.analyze_rgb(&rgb, width, height, timestamp_ms)?;
+        analysis.frame_id = frame_id;
+        Ok(analysis)
+    }
+
+    /// Process frame with Waldo Vision's sophisticated multi-layer analysis and cooldown logic
+    pub fn process_frame(&mut self, frame_b64: String, timestamp_ms: u64) -> PyResult<(bool, f32, usize)> {
+        let analysis = self.core.analyze_frame(&frame_b64, timestamp_ms)?;
+
+        // Return: (trigger_ai, confidence_score, tracked_objects_count)
+        Ok((analysis.should_trigger, analysis.confidence, analysis.tracked_objects))
+    }
+
+    /// Process frame and return the full per-frame result object. `frame_id` is an opaque
+    /// caller tag copied verbatim onto the result for correlation.
+    #[pyo3(signature = (frame_b64, timestamp_ms, frame_id=None))]
+    pub fn analyze_frame(&mut self, frame_b64: String, timestamp_ms: u64, frame_id: Option<String>) -> PyResult<FrameAnalysis> {
+        let mut analysis = self.core.analyze_frame(&frame_b64, timestamp_ms)?;
+        analysis.frame_id = frame_id;
+        Ok(analysis)
+    }
+
+    /// Process a frame and return only its scene-state code (0=Calibrating, 1=Stable,
+    /// 2=Volatile, 3=Disturbed). Skips triggering, blobs and the result object; see README
+    /// "Quick State".
+    pub fn quick_state(&mut self, frame_b64: String, timestamp_ms: u64) -> PyResult<u8> {
+        Ok(self.core.quick_state(&frame_b64, timestamp_ms)?)
+    }
+
+    /// Feed `(frame_b64, timestamp_ms)` items from a Python iterable until the scene first
+    /// reports STABLE; returns the detector's frame_count at that point
+    pub fn process_until_stable(&mut self, frame_iter: &Bound<'_, PyAny>, max_frames: u64) -> PyResult<u64> {
+        let mut frames = frame_iter.iter()?;
+        for _ in 0..max_frames {
+            let Some(item) = frames.next() else {
+                return Err(PyErr::new::<pyo3::exceptions::PyTimeoutError, _>(format!(
+                    "Frame source exhausted before scene reached STABLE (frame_count={})", self.core.frame_count()
+                )));
+            };
+            let (frame_b64, timestamp_ms): (String, u64) = item?.extract()?;
+            // Same path as process_frame, so cooldowns and triggers still apply while waiting
+            let analysis = self.core.analyze_frame(&frame_b64, timestamp_ms)?;
+            if analysis.scene_state == "STABLE" {
+                return Ok(analysis.frame_count);
+            }
+        }
+        Err(PyErr::new::<pyo3::exceptions::PyTimeoutError, _>(format!(
+            "Scene did not reach STABLE within {} frames", max_frames
+        )))
+    }
+
+    /// Lazily process `(frame_b64, timestamp_ms)` items from a Python iterable, yielding
+    /// a `FrameAnalysis` per frame: `for analysis in detector.stream(source): ...`
+    pub fn stream(slf: &Bound<'_, Self>, frame_source: &Bound<'_, PyAny>) -> PyResult<FrameStream> {
+        Ok(FrameStream {
+            detector: slf.clone().unbind(),
+            source: frame_source.iter()?.unbind(),
+        })
+    }
+
+    /// Process `(frame_b64, timestamp_ms)` items from a Python iterable and return their
+    /// results as a list. With `max_in_flight`, at most that many frames are pulled per
+    /// call; pass the same iterator again for the next chunk (an empty list means done).
+    #[pyo3(signature = (frames, max_in_flight=None))]
+    pub fn process_batch(slf: &Bound<'_, Self>, frames: &Bound<'_, PyAny>, max_in_flight: Option<usize>) -> PyResult<Vec<FrameAnalysis>> {
+        let mut source = frames.iter()?;
+        let mut results = Vec::new();
+        while max_in_flight.is_none_or(|limit| results.len() < limit) {
+            // Pull before borrowing the detector, so the source may itself use the detector
+            let Some(item) = source.next() else { break };
+            let (frame_b64, timestamp_ms): (String, u64) = item?.extract()?;
+            let analysis = slf.borrow_mut().core.analyze_frame_recorded(&frame_b64, timestamp_ms)?;
+            results.push(analysis);
+        }
+        Ok(results)
+    }
+
+    /// Like `process_batch`, but return the results as an Arrow record batch with the
+    /// columns listed in README "Arrow Results"; needs the `arrow` feature
+    #[cfg(feature = "arrow")]
+    #[pyo3(signature = (frames, max_in_flight=None))]
+    pub fn process_batch_arrow(slf: &Bound<'_, Self>, frames: &Bound<'_, PyAny>, max_in_flight: Option<usize>) -> PyResult<crate::arrow::AnalysisBatch> {
+        let results = Self::process_batch(slf, frames, max_in_flight)?;
+        Ok(crate::arrow::AnalysisBatch::new(&results))
+    }
+
+    /// Process `(frame_b64, timestamp_ms)` items from a Python iterable, handing each
+    /// result to `callback` instead of collecting them; returns the number of frames processed
+    pub fn process_batch_streaming(slf: &Bound<'_, Self>, frames: &Bound<'_, PyAny>, callback: &Bound<'_, PyAny>) -> PyResult<u64> {
+        let mut processed = 0;
+        for item in frames.iter()? {
+            let (frame_b64, timestamp_ms): (String, u64) = item?.extract()?;
+            let analysis = slf.borrow_mut().core.analyze_frame_recorded(&frame_b64, timestamp_ms)?;
+            // Detector borrow is released here, so the callback may query the detector
+            callback.call1((analysis,))?;
+            processed += 1;
+        }
+        Ok(processed)
+    }
+
+    /// Recorded batch result for `frame_count == index` (see README "Recorded Results");
+    /// None when recording is off, the frame was evicted or never recorded
+    pub fn get_recorded_analysis(&self, index: u64) -> Option<FrameAnalysis> {
+        self.core.recorded_analysis(index).cloned()
+    }
+
+    /// (first, last) recorded frame_count, or None when nothing is recorded
+    pub fn get_recorded_range(&self) -> Option<(u64, u64)> {
+        self.core.recorded_range()
+    }
+
+    /// Take every queued trigger, oldest first, coalescing triggers at most `debounce_ms`
+    /// apart into one event (see README "Trigger Queue"); empty when the queue is off
+    #[pyo3(signature = (debounce_ms=None))]
+    pub fn drain_triggers(&mut self, debounce_ms: Option<u64>) -> Vec<TriggerEvent> {
+        self.core.drain_triggers(debounce_ms)
+    }
+
+    /// Write every fired trigger to the Unix domain socket at `path` as a JSON line, from
+    /// within the frame call that fires it; None disconnects (see README "Event Sink")
+    #[pyo3(signature = (path))]
+    pub fn set_event_sink(&mut self, path: Option<std::path::PathBuf>) -> PyResult<()> {
+        Ok(self.core.set_event_sink(path.as_deref())?)
+    }
+
+    /// Delivery counters of the event sink, or None without one
+    pub fn get_event_sink_stats(&self) -> Option<EventSinkStats> {
+        self.core.event_sink_stats()
+    }
+
+    /// Configure Waldo Vision pipeline (simplified interface)
+    pub fn configure(&mut self,
+                    _buffer_duration_ms: Option<u64>,
+                    _change_threshold: Option<f32>,
+                    _frame_interval_ms: Option<u64>) -> PyResult<()> {
+        // None of these are applied yet; strict mode says so instead of accepting them
+        if self.core.config().strict && (_buffer_duration_ms.is_some() || _change_threshold.is_some() || _frame_interval_ms.is_some()) {
+            return Err(ConfigError::new_err("configure() does not apply any options yet; they would be ignored (strict mode)"));
+        }
+        // Note: Waldo Vision pipeline would need to be recreated for config changes
+        // For now, store the values for future pipeline recreation
+        Ok(())
+    }
+
+    /// Get current configuration and processing stats
+    pub fn get_config(&self) -> PyResult<(u64, f32, u64)> {
+        Ok((
+            self.core.frame_count(),    // Total frames processed by Waldo Vision
+            5.0,                        // Default change threshold
+            33,                         // 30fps interval (480p smooth)
+        ))
+    }
+
+    /// Release the pipelines and caches; any later frame processing raises
+    /// `DetectorClosedError`. Idempotent. Introspection methods keep working.
+    pub fn close(&mut self) {
+        self.core.close();
+    }
+
+    fn __enter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    /// Close on leaving a `with` block; exceptions from the block are not suppressed
+    fn __exit__(&mut self, _exc_type: &Bound<'_, PyAny>, _exc_value: &Bound<'_, PyAny>, _traceback: &Bound<'_, PyAny>) -> bool {
+        self.core.close();
+        false
+    }
+
+    /// Reset Waldo Vision pipeline state
+    pub fn reset(&mut self) {
+        self.core.reset();
+    }
+
+    /// Get Waldo Vision scene analysis state
+    pub fn get_analysis_info(&self) -> PyResult<(String, u64)> {
+        Ok((
+            "Waldo Vision Multi-Layer Analysis Active".to_string(),
+            self.core.frame_count()
+        ))
+    }
+
+    /// Fully resolved Waldo Vision `PipelineConfig` (thresholds already converted from
+    /// percent). Read from the created pipeline once the first frame locked in the image
+    /// dimensions (`"source": "pipeline"`), else from the template (`"source": "template"`).
+    pub fn get_effective_config<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let (config, source) = self.core.effective_pipeline_config();
+        let dict = PyDict::new_bound(py);
+        dict.set_item("source", source)?;
+        dict.set_item("image_width", config.image_width)?;
+        dict.set_item("image_height", config.image_height)?;
+        dict.set_item("chunk_width", config.chunk_width)?;
+        dict.set_item("chunk_height", config.chunk_height)?;
+        dict.set_item("new_age_threshold", config.new_age_threshold)?;
+        dict.set_item("behavioral_anomaly_threshold", config.behavioral_anomaly_threshold)?;
+        dict.set_item("absolute_min_blob_size", config.absolute_min_blob_size)?;
+        dict.set_item("blob_size_std_dev_filter", config.blob_size_std_dev_filter)?;
+        dict.set_item("disturbance_entry_threshold", config.disturbance_entry_threshold)?;
+        dict.set_item("disturbance_exit_threshold", config.disturbance_exit_threshold)?;
+        dict.set_item("disturbance_confirmation_frames", config.disturbance_confirmation_frames)?;
+        Ok(dict)
+    }
+
+    /// Suggest a chunk grid for a frame size: `(columns, rows, chunk_width, chunk_height)` with
+    /// chunks close to `target_chunk_pixels` in area, near-square, and preferably dividing the
+    /// frame evenly (see README "Grid Suggestions")
+    #[staticmethod]
+    #[pyo3(signature = (width, height, target_chunk_pixels=100))]
+    pub fn suggest_grid(width: u32, height: u32, target_chunk_pixels: u32) -> PyResult<(u32, u32, u32, u32)> {
+        Ok(CoreDetector::suggest_grid(width, height, target_chunk_pixels)?)
+    }
+
+    /// Get current scene state and cooldown status
+    pub fn get_scene_status(&self) -> PyResult<(String, f64, f64)> {
+        let current_time = self.core.clock_secs(None);
+        let (last_volatile_trigger, last_disturbed_trigger) = self.core.cooldown_timers();
+
+        let volatile_cooldown_remaining = cooldown_remaining(last_volatile_trigger, VOLATILE_COOLDOWN_SECS, current_time);
+        let disturbed_cooldown_remaining = cooldown_remaining(last_disturbed_trigger, DISTURBED_COOLDOWN_SECS, current_time);
+
+        Ok((
+            "MONITORING".to_string(), // Scene state would need pipeline exposure
+            volatile_cooldown_remaining,
+            disturbed_cooldown_remaining
+        ))
+    }
+
+    /// Drop the learned background so the pipeline calibrates again from the next frame,
+    /// keeping counters and cooldowns; re-arms `alert_on_first_motion`
+    pub fn recalibrate(&mut self) {
+        self.core.recalibrate();
+    }
+
+    /// Let the next Disturbed frame trigger immediately, without a full `reset()`
+    pub fn clear_disturbed_cooldown(&mut self) {
+        self.core.clear_disturbed_cooldown();
+    }
+
+    /// Let the next Volatile trigger fire immediately, without a full `reset()`
+    pub fn clear_volatile_cooldown(&mut self) {
+        self.core.clear_volatile_cooldown();
+    }
+
+    /// Seconds since the latest frame with `should_trigger` set, on the cooldown clock
+    /// (frame timestamps in deterministic mode); None if nothing has triggered yet
+    pub fn seconds_since_last_trigger(&self) -> Option<f64> {
+        self.core.seconds_since_last_trigger()
+    }
+
+    /// Raw cooldown timers: (last_volatile_trigger, last_disturbed_trigger), in seconds on the
+    /// cooldown clock (frame timestamps in deterministic mode); None means the timer has
+    /// never fired or was cleared
+    pub fn get_cooldown_timers(&self) -> PyResult<(Option<f64>, Option<f64>)> {
+        Ok(self.core.cooldown_timers())
+    }
+
+    /// Blobs reported for the most recent frame (the same list as its `analysis.blobs`)
+    pub fn get_tracked_blobs(&self) -> Vec<BlobInfo> {
+        self.core.tracked_blobs()
+    }
+
+    /// Up to `n` chunks of the latest frame that changed the most, largest change first;
+    /// fewer on a calm frame (see README "Hotspots")
+    pub fn get_hotspots(&self, n: usize) -> Vec<Hotspot> {
+        self.core.hotspots(n)
+    }
+
+    /// Waldo Vision's `Report` for the latest analyzed frame as a dict tagged by `"variant"`,
+    /// with the variant's fields alongside; None before the first frame, after a pipeline
+    /// rebuild and in low-memory mode (see README "Pipeline Reports")
+    pub fn get_last_report<'py>(&self, py: Python<'py>) -> PyResult<Option<Bound<'py, PyDict>>> {
+        let Some(report) = self.core.last_report() else {
+            return Ok(None);
+        };
+        let dict = PyDict::new_bound(py);
+        match report {
+            Report::NoSignificantMention => dict.set_item("variant", "NoSignificantMention")?,
+            Report::SignificantMention(mention) => {
+                dict.set_item("variant", "SignificantMention")?;
+                let moments = |moments: &[Moment]| moments.iter().map(|moment| moment_dict(py, moment)).collect::<PyResult<Vec<_>>>();
+                dict.set_item("new_significant_moments", moments(&mention.new_significant_moments)?)?;
+                dict.set_item("completed_significant_moments", moments(&mention.completed_significant_moments)?)?;
+                dict.set_item("is_global_disturbance", mention.is_global_disturbance)?;
+            }
+        }
+        Ok(Some(dict))
+    }
+
+    /// `(step, parameters)` for each preprocessing step that currently changes the frame, in
+    /// the order they run (see README "Preprocessing Chain")
+    pub fn get_preprocessing_chain(&self) -> Vec<(PreprocessStep, String)> {
+        self.core.preprocessing_chain()
+    }
+
+    /// Name of the `sensitivity_schedule` profile in effect, or None for the base options
+    /// (see README "Sensitivity Schedule")
+    pub fn get_active_profile(&self) -> Option<String> {
+        self.core.active_profile().map(str::to_string)
+    }
+
+    /// Whether the latest frame met the trigger conditions, cooldowns aside; false before
+    /// any frame. Reads the cached result only, so it never touches the cooldown timers
+    pub fn would_trigger_ignoring_cooldown(&self) -> bool {
+        self.core.would_trigger_ignoring_cooldown()
+    }
+
+    /// Seed a fresh pipeline with a known-clean background so live frames are analyzed
+    /// immediately instead of after calibration (see README "Reference Frames").
+    /// The frame must match the locked frame size; mid-stream, it replaces the pipeline.
+    pub fn set_reference_frame(&mut self, frame_b64: String) -> PyResult<()> {
+        Ok(self.core.set_reference_frame(&frame_b64)?)
+    }
+
+    /// Seed a fresh pipeline with several frames of the normal scene, so calibration learns
+    /// its usual variation (see README "Seeded Calibration"). All frames must share the
+    /// locked frame size; mid-stream, it replaces the pipeline.
+    pub fn seed_calibration(&mut self, frames: Vec<String>) -> PyResult<()> {
+        Ok(self.core.seed_calibration(&frames)?)
+    }
+
+    /// Restrict analysis to a rectangle of decoded-frame pixels (see README "Region of Interest").
+    /// Clipped to the frame once its size is known; before the first frame, validation is
+    /// deferred until it arrives.
+    pub fn set_roi(&mut self, x: i64, y: i64, width: u32, height: u32) -> PyResult<()> {
+        Ok(self.core.set_roi(x, y, width, height)?)
+    }
+
+    /// Analyze the whole frame again
+    pub fn clear_roi(&mut self) {
+        self.core.clear_roi();
+    }
+
+    /// Effective (clipped) ROI as (x, y, width, height); None when unset or still deferred
+    pub fn get_roi(&self) -> Option<(u32, u32, u32, u32)> {
+        self.core.roi()
+    }
+
+    /// ROI passed to `set_roi` before the first frame, as given (unclipped); None once the
+    /// frame size is known and it has been applied
+    pub fn get_pending_roi(&self) -> Option<(i64, i64, u32, u32)> {
+        self.core.pending_roi()
+    }
+
+    /// Exclude a polygon (vertices in decoded-frame pixels) from analysis; its pixels are
+    /// zeroed before the pipeline sees them. Several polygons combine into one mask.
+    pub fn add_exclusion_polygon(&mut self, points: Vec<(u32, u32)>) -> PyResult<()> {
+        Ok(self.core.add_exclusion_polygon(points)?)
+    }
+
+    /// Remove every exclusion polygon
+    pub fn clear_exclusion_polygons(&mut self) {
+        self.core.clear_exclusion_polygons();
+    }
+
+    /// Exclusion polygons in insertion order
+    pub fn get_exclusion_polygons(&self) -> Vec<Vec<(u32, u32)>> {
+        self.core.exclusion_polygons().to_vec()
+    }
+
+    /// Same as `get_exclusion_polygons`
+    pub fn get_exclusion_zones(&self) -> Vec<Vec<(u32, u32)>> {
+        self.get_exclusion_polygons()
+    }
+
+    /// Same as `clear_exclusion_polygons`
+    pub fn clear_exclusion_zones(&mut self) {
+        self.clear_exclusion_polygons();
+    }
+
+    /// Add (or replace) a named region, in decoded-frame pixels, with its own trigger cooldown
+    pub fn add_region(&mut self, name: String, x: u32, y: u32, width: u32, height: u32) -> PyResult<()> {
+        Ok(self.core.add_region(name, x, y, width, height)?)
+    }
+
+    /// Remove a named region; returns whether it existed
+    pub fn remove_region(&mut self, name: &str) -> bool {
+        self.core.remove_region(name)
+    }
+
+    /// Get remaining cooldown (seconds) for every region, keyed by name
+    pub fn get_region_cooldowns(&self) -> PyResult<std::collections::BTreeMap<String, f64>> {
+        Ok(self.core.region_cooldowns())
+    }
+
+    /// Get decode cache counters: (hits, misses, cached_frames); all 0 while the cache is off
+    pub fn get_decode_cache_stats(&self) -> PyResult<(u64, u64, usize)> {
+        Ok(self.core.decode_cache_stats())
+    }
+
+    /// Get decode path counters: (grayscale_fast_path_frames, color_converted_frames), by
+    /// whether the source was single-channel, whichever backend decoded it
+    pub fn get_decode_stats(&self) -> PyResult<(u64, u64)> {
+        Ok(self.core.decode_stats())
+    }
+
+    /// What the pipeline currently takes as background: each chunk filled with its mean luma
+    /// over the pipeline's chunk history, as a base64 grayscale PNG in analysis coordinates.
+    /// A debugging aid (see README "Background Model"); None before the first analyzed
+    /// frame and in low-memory mode.
+    pub fn export_background_frame(&self) -> Option<String> {
+        self.core.export_background_frame()
+    }
+
+    /// Get timestamp diagnostics: out-of-order/duplicate counts and min/max forward delta (ms)
+    pub fn get_timing_diagnostics<'py>(&self, py: Python<'py>) -> PyResult<Bound<'py, PyDict>> {
+        let timing = self.core.timing_diagnostics();
+        let stats = PyDict::new_bound(py);
+        stats.set_item("out_of_order_frames", timing.out_of_order_frames)?;
+        stats.set_item("duplicate_timestamps", timing.duplicate_timestamps)?;
+        stats.set_item("skipped_duplicates", timing.skipped_duplicates)?;
+        stats.set_item("min_delta_ms", timing.min_delta_ms)?;
+        stats.set_item("max_delta_ms", timing.max_delta_ms)?;
+        stats.set_item("last_timestamp_ms", timing.last_timestamp_ms)?;
+        stats.set_item("estimated_fps", timing.estimated_fps)?;
+        Ok(stats)
+    }
+
+    /// Source frame rate from the rolling median of recent timestamp deltas (see README
+    /// "Frame Rate Estimation"); None until a few frames arrive, and in low-memory mode
+    pub fn estimated_fps(&self) -> Option<f32> {
+        self.core.estimated_fps()
+    }
+
+    /// Process frame and return results with scene state for logging
+    pub fn process_frame_with_state(&mut self, frame_b64: String, timestamp_ms: u64) -> PyResult<(bool, f32, usize, String)> {
+        let analysis = self.core.analyze_frame(&frame_b64, timestamp_ms)?;
+
+        // Return: (trigger_ai, confidence_score, tracked_objects_count, scene_state)
+        Ok((analysis.should_trigger, analysis.confidence, analysis.tracked_objects, analysis.scene_state))
+    }
+}
+
+/// Iterator returned by `FrameChangeDetector.stream`; pulls one source frame per step
+#[pyclass]
+pub struct FrameStream {
+    detector: Py<FrameChangeDetector>,
+    source: Py<pyo3::types::PyIterator>,
+}
+
+#[pymethods]
+impl FrameStream {
+    fn __iter__(slf: PyRef<'_, Self>) -> PyRef<'_, Self> {
+        slf
+    }
+
+    fn __next__(&self, py: Python<'_>) -> PyResult<Option<FrameAnalysis>> {
+        // Pull before borrowing the detector, so the source may itself use the detector
+        let Some(item) = self.source.bind(py).clone().next() else {
+            return Ok(None); // Source exhausted: StopIteration
+        };
+        let (frame_b64, timestamp_ms): (String, u64) = item?.extract()?;
+        let analysis = self.detector.bind(py).borrow_mut().core.analyze_frame(&frame_b64, timestamp_ms)?;
+        Ok(Some(analysis))
+    }
+}
+
+/// Input formats this build can analyze: encoded image formats the `image` crate was compiled
+/// to read (for `analyze_frame`, `process_frame` and `process_image_file`), then the raw
+/// pixel layouts (see README "Build Capabilities")
+#[pyfunction]
+fn supported_input_formats() -> Vec<String> {
+    crate::supported_input_formats()
+}
+
+/// Optional cargo features compiled into this build
+#[pyfunction]
+fn supported_features() -> Vec<String> {
+    crate::supported_features()
+}
+
+/// A Waldo Vision `Moment` as a dict of its fields, coordinates in pipeline grid chunks
+fn moment_dict<'py>(py: Python<'py>, moment: &Moment) -> PyResult<Bound<'py, PyDict>> {
+    let dict = PyDict::new_bound(py);
+    dict.set_item("id", moment.id)?;
+    dict.set_item("start_frame", moment.start_frame)?;
+    dict.set_item("end_frame", moment.end_frame)?;
+    dict.set_item("path", &moment.path)?;
+    dict.set_item("blob_history", moment.blob_history.iter().map(|blob| smart_blob_dict(py, blob)).collect::<PyResult<Vec<_>>>()?)?;
+    dict.set_item("is_active", moment.is_active)?;
+    dict.set_item("is_significant", moment.is_significant)?;
+    Ok(dict)
+}
+
+/// A Waldo Vision `SmartBlob` as a dict of its fields, coordinates in pipeline grid chunks
+fn smart_blob_dict<'py>(py: Python<'py>, blob: &SmartBlob) -> PyResult<Bound<'py, PyDict>> {
+    let (top_left, bottom_right) = &blob.bounding_box;
+    let dict = PyDict::new_bound(py);
+    dict.set_item("id", blob.id)?;
+    dict.set_item("bounding_box", ((top_left.x, top_left.y), (bottom_right.x, bottom_right.y)))?;
+    dict.set_item("chunk_coords", blob.chunk_coords.iter().map(|point| (point.x, point.y)).collect::<Vec<_>>())?;
+    dict.set_item("size_in_chunks", blob.size_in_chunks)?;
+    let anomaly = PyDict::new_bound(py);
+    anomaly.set_item("luminance_score", blob.average_anomaly.luminance_score)?;
+    anomaly.set_item("color_score", blob.average_anomaly.color_score)?;
+    anomaly.set_item("hue_score", blob.average_anomaly.hue_score)?;
+    dict.set_item("average_anomaly", anomaly)?;
+    dict.set_item("center_of_mass", blob.center_of_mass)?;
+    Ok(dict)
+}
+
+/// Python module
+#[pymodule]
+fn frame_change_detector(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    // Forward `log` records to Python's `logging` (logger "frame_change_detector")
+    pyo3_log::init();
+    m.add_class::<FrameChangeDetector>()?;
+    m.add_class::<FrameAnalysis>()?;
+    m.add_class::<FrameStream>()?;
+    m.add_class::<BlobInfo>()?;
+    m.add_class::<Hotspot>()?;
+    m.add_class::<TriggerEvent>()?;
+    m.add_class::<EventSinkStats>()?;
+    #[cfg(feature = "arrow")]
+    m.add_class::<crate::arrow::AnalysisBatch>()?;
+    m.add_class::<OutputCoords>()?;
+    m.add_class::<ChannelCombine>()?;
+    m.add_class::<BackgroundModel>()?;
+    m.add_class::<ConfidenceCurve>()?;
+    m.add_class::<SensitivityProfile>()?;
+    m.add_class::<SensitivitySchedule>()?;
+    m.add_class::<DecoderBackend>()?;
+    m.add_class::<DetectorConfig>()?;
+    m.add_class::<Equalization>()?;
+    m.add_class::<PreprocessStep>()?;
+    m.add_class::<ResizeFilter>()?;
+    m.add_class::<Rotation>()?;
+    m.add_class::<BlobRanking>()?;
+    m.add_class::<UniformFramePolicy>()?;
+    m.add_class::<FrameSizePolicy>()?;
+    m.add_class::<TruncatedJpegPolicy>()?;
+    m.add_class::<DuplicateTimestampPolicy>()?;
+    m.add_class::<EventSinkPolicy>()?;
+    m.add_class::<FirstFramePolicy>()?;
+    m.add_function(wrap_pyfunction!(supported_input_formats, m)?)?;
+    m.add_function(wrap_pyfunction!(supported_features, m)?)?;
+    m.add("CorpusError", m.py().get_type_bound::<CorpusError>())?;
+    m.add("DecodeError", m.py().get_type_bound::<DecodeError>())?;
+    m.add("ConfigError", m.py().get_type_bound::<ConfigError>())?;
+    m.add("DimensionMismatchError", m.py().get_type_bound::<DimensionMismatchError>())?;
+    m.add("CalibrationError", m.py().get_type_bound::<CalibrationError>())?;
+    m.add("DetectorClosedError", m.py().get_type_bound::<DetectorClosedError>())?;
+    m.add("DuplicateTimestampError", m.py().get_type_bound::<DuplicateTimestampError>())?;
+    m.add("SerializeError", m.py().get_type_bound::<SerializeError>())?;
+    m.add("PipelinePanicError", m.py().get_type_bound::<PipelinePanicError>())?;
+    Ok(())
+}